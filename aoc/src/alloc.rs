@@ -0,0 +1,195 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::fmt;
+
+/// Heap activity of a measured section of code
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    /// Number of allocations, reallocations included
+    pub allocations: u64,
+    /// Total bytes requested
+    pub bytes: u64,
+    /// Highest heap growth over the start of the section
+    pub peak: u64,
+}
+
+impl AllocStats {
+    /// Stats of this section followed by the next one, the peak is the higher of the two
+    pub fn then(self, next: AllocStats) -> AllocStats {
+        AllocStats {
+            allocations: self.allocations + next.allocations,
+            bytes: self.bytes + next.bytes,
+            peak: self.peak.max(next.peak),
+        }
+    }
+}
+
+impl fmt::Display for AllocStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "peak: {}, allocations: {}, allocated: {}",
+            format_bytes(self.peak),
+            self.allocations,
+            format_bytes(self.bytes)
+        )
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Counters {
+    allocations: u64,
+    bytes: u64,
+    current: i64,
+    peak: i64,
+}
+
+const ZERO: Counters = Counters {
+    allocations: 0,
+    bytes: 0,
+    current: 0,
+    peak: 0,
+};
+
+thread_local! {
+    // Per thread so parts running in parallel don't count each other,
+    // const initialized so the allocator never allocates to reach it
+    static COUNTERS: Cell<Counters> = const { Cell::new(ZERO) };
+}
+
+/// Wraps the system allocator, counting the allocations of the current thread.
+///
+/// Installed as the global allocator with the `alloc-stats` feature.
+pub struct CountingAlloc;
+
+impl CountingAlloc {
+    fn track(allocated: usize, freed: usize) {
+        let _ = COUNTERS.try_with(|counters| {
+            let mut c = counters.get();
+            if allocated > 0 {
+                c.allocations += 1;
+                c.bytes += allocated as u64;
+            }
+            c.current += allocated as i64 - freed as i64;
+            c.peak = c.peak.max(c.current);
+            counters.set(c);
+        });
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            Self::track(layout.size(), 0);
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            Self::track(layout.size(), 0);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        Self::track(0, layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            Self::track(new_size, layout.size());
+        }
+        new_ptr
+    }
+}
+
+/// Whether the counting allocator is installed
+pub fn enabled() -> bool {
+    cfg!(feature = "alloc-stats")
+}
+
+/// Runs `f` and returns the heap activity it caused on the current thread.
+///
+/// Stats are all zero when the counting allocator is not installed.
+pub fn measure<T, F>(f: F) -> (T, AllocStats)
+where
+    F: FnOnce() -> T,
+{
+    COUNTERS.with(|counters| counters.set(ZERO));
+    let result = f();
+    let c = COUNTERS.with(|counters| counters.get());
+
+    let stats = AllocStats {
+        allocations: c.allocations,
+        bytes: c.bytes,
+        peak: c.peak.max(0) as u64,
+    };
+    (result, stats)
+}
+
+/// Formats a byte count using binary units, ie: `1.5 KiB`
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    let formatted = format!("{:.2}", value);
+    format!(
+        "{} {}",
+        formatted.trim_end_matches('0').trim_end_matches('.'),
+        UNITS[unit]
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_measure() {
+        let alloc = CountingAlloc;
+        let layout = Layout::from_size_align(64, 8).unwrap();
+
+        let (_, stats) = measure(|| unsafe {
+            let a = alloc.alloc(layout);
+            let b = alloc.alloc_zeroed(layout);
+            alloc.dealloc(a, layout);
+            let b = alloc.realloc(b, layout, 256);
+            alloc.dealloc(b, Layout::from_size_align(256, 8).unwrap());
+        });
+
+        // Allocations made through the real global allocator only count with the feature
+        if !enabled() {
+            assert_eq!(
+                stats,
+                AllocStats {
+                    allocations: 3,
+                    bytes: 64 + 64 + 256,
+                    peak: 256,
+                }
+            );
+        }
+        assert!(stats.allocations >= 3);
+        assert!(stats.peak >= 256);
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(0), "0 B".to_string());
+        assert_eq!(format_bytes(1023), "1023 B".to_string());
+        assert_eq!(format_bytes(1536), "1.5 KiB".to_string());
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3 MiB".to_string());
+    }
+}
@@ -0,0 +1,255 @@
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::days::Day;
+use crate::report::Record;
+use crate::selector::InputSelector;
+use crate::{ParsedDay, PartRun, RunError};
+
+/// Parses a number of seconds, ie: `5` or `0.25`
+pub fn parse_seconds(value: &str) -> Result<Duration, String> {
+    let seconds: f64 = value
+        .parse()
+        .map_err(|_| format!("`{}` is not a number of seconds", value))?;
+    Duration::try_from_secs_f64(seconds).map_err(|_| format!("`{}` is not a valid duration", value))
+}
+
+/// Runs a part on its own thread, giving up when it takes longer than the budget.
+///
+/// Returns `None` when the part exceeds the budget. The solver can't be interrupted so its thread
+/// is left running in the background until the process exits.
+pub fn run_part_within(
+    day: &'static Day,
+    part: u8,
+    selector: &InputSelector,
+    budget: Duration,
+) -> Option<Result<PartRun, RunError>> {
+    match run_parts_within(day, &[part], selector, budget, false) {
+        Ok(mut runs) => runs.remove(0),
+        Err(err) => Some(Err(err)),
+    }
+}
+
+/// Runs the parts of a day against a single parse of its input, all within the budget.
+///
+/// The budget covers reading and parsing the input as well as every part, so a day never takes
+/// longer than the budget. A part still running when it runs out is `None`. With `parallel` each
+/// part runs concurrently on a thread of its own.
+pub fn run_parts_within(
+    day: &'static Day,
+    parts: &[u8],
+    selector: &InputSelector,
+    budget: Duration,
+    parallel: bool,
+) -> Result<Vec<Option<Result<PartRun, RunError>>>, RunError> {
+    let (sender, receiver) = mpsc::channel();
+    let selector = selector.clone();
+    let to_solve = parts.to_vec();
+
+    thread::spawn(move || {
+        let parsed = match ParsedDay::new(day, &selector) {
+            Ok(parsed) => parsed,
+            Err(err) => {
+                let _ = sender.send(Err(err));
+                return;
+            }
+        };
+        // The receiver is gone when the budget ran out, nobody to tell
+        let send = |(k, part): (usize, &u8)| {
+            sender
+                .send(Ok((k, parsed.solve(*part, k == 0))))
+                .map_err(|_| ())
+        };
+        if parallel {
+            // Threads of their own rather than the rayon pool, its workers may all be blocked
+            // waiting on days run within a budget
+            thread::scope(|scope| {
+                for solve in to_solve.iter().enumerate() {
+                    scope.spawn(move || send(solve));
+                }
+            });
+        } else {
+            let _ = to_solve.iter().enumerate().try_for_each(send);
+        }
+    });
+
+    let deadline = Instant::now() + budget;
+    let mut runs: Vec<Option<Result<PartRun, RunError>>> = Vec::with_capacity(parts.len());
+    runs.resize_with(parts.len(), || None);
+    for _ in parts.iter() {
+        // A busy machine may deliver a late result before the wait times out,
+        // the run's own timings are what count against the budget
+        match receiver.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+            Ok(Err(err)) => return Err(err),
+            Ok(Ok((_, Ok(run)))) if run.timings.total() > budget => {}
+            Ok(Ok((k, result))) => runs[k] = Some(result),
+            Err(_) => break,
+        }
+    }
+    Ok(runs)
+}
+
+/// Parts that ran but took longer than the threshold, slowest first
+pub fn slow_parts(records: &[Record], threshold: Duration) -> Vec<&Record> {
+    let mut slow: Vec<&Record> = records
+        .iter()
+        .filter(|r| r.duration().is_some_and(|d| d > threshold))
+        .collect();
+    slow.sort_by_key(|r| std::cmp::Reverse(r.duration()));
+    slow
+}
+
+#[cfg(test)]
+mod tests {
+    use input::{parse_puzzle, Answer, Solution, SolveError};
+
+    use super::*;
+
+    struct Sleepy;
+
+    impl Solution for Sleepy {
+        type Parsed = u64;
+
+        fn parse(input: &str) -> Result<Self::Parsed, SolveError> {
+            input
+                .trim()
+                .parse()
+                .map_err(|_| SolveError::Parse(format!("`{}` is not a number", input.trim())))
+        }
+
+        fn part1(parsed: &Self::Parsed) -> Result<Answer, SolveError> {
+            thread::sleep(Duration::from_millis(*parsed));
+            Ok((*parsed).into())
+        }
+
+        fn part2(parsed: &Self::Parsed) -> Result<Answer, SolveError> {
+            Self::part1(parsed)
+        }
+    }
+
+    static SLEEPY: Day = Day {
+        day: 99,
+        parse: parse_puzzle::<Sleepy>,
+    };
+
+    fn record(day: u8, millis: u64) -> Record {
+        Record {
+            day,
+            part: 1,
+            input: format!("{:02}", day),
+            answer: Some("1".to_string()),
+            read: None,
+            parse: None,
+            solve: Some(Duration::from_millis(millis)),
+            allocs: None,
+            status: "ok",
+        }
+    }
+
+    #[test]
+    fn test_parse_seconds() {
+        assert_eq!(parse_seconds("5"), Ok(Duration::from_secs(5)));
+        assert_eq!(parse_seconds("0.25"), Ok(Duration::from_millis(250)));
+        assert!(parse_seconds("-1").is_err());
+        assert!(parse_seconds("soon").is_err());
+    }
+
+    #[test]
+    fn test_run_part_within() {
+        let dir = std::env::temp_dir().join(format!("aoc-budget-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let fast = dir.join("fast.txt");
+        std::fs::write(&fast, "1").unwrap();
+        let run = run_part_within(
+            &SLEEPY,
+            1,
+            &InputSelector::Path(fast),
+            Duration::from_secs(5),
+        );
+        assert_eq!(run.unwrap().unwrap().answer, Answer::from(1));
+
+        let slow = dir.join("slow.txt");
+        std::fs::write(&slow, "2000").unwrap();
+        let run = run_part_within(
+            &SLEEPY,
+            2,
+            &InputSelector::Path(slow),
+            Duration::from_millis(50),
+        );
+        assert!(run.is_none());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_run_parts_within() {
+        let dir = std::env::temp_dir().join(format!("aoc-parts-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("input.txt");
+        std::fs::write(&path, "1").unwrap();
+
+        for parallel in [false, true] {
+            let runs = run_parts_within(
+                &SLEEPY,
+                &[1, 2],
+                &InputSelector::Path(path.clone()),
+                Duration::from_secs(5),
+                parallel,
+            )
+            .unwrap();
+            let runs: Vec<PartRun> = runs.into_iter().map(|r| r.unwrap().unwrap()).collect();
+            // The input is read and parsed once, with the first part
+            assert_eq!(runs[0].part, 1);
+            assert!(runs[0].timings.get("parse").is_some());
+            assert_eq!(runs[1].part, 2);
+            assert!(runs[1].timings.get("read").is_none());
+            assert!(runs[1].timings.get("parse").is_none());
+            assert!(runs[1].timings.get("solve").is_some());
+        }
+
+        std::fs::write(&path, "2000").unwrap();
+        for parallel in [false, true] {
+            let runs = run_parts_within(
+                &SLEEPY,
+                &[1, 2],
+                &InputSelector::Path(path.clone()),
+                Duration::from_millis(50),
+                parallel,
+            )
+            .unwrap();
+            assert!(runs.iter().all(|r| r.is_none()));
+        }
+
+        // One budget for the whole day, the second part has what the first left of it
+        std::fs::write(&path, "400").unwrap();
+        let runs = run_parts_within(
+            &SLEEPY,
+            &[1, 2],
+            &InputSelector::Path(path.clone()),
+            Duration::from_millis(600),
+            false,
+        )
+        .unwrap();
+        assert!(runs[0].is_some());
+        assert!(runs[1].is_none());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_slow_parts() {
+        let records = vec![
+            record(1, 500),
+            record(2, 1500),
+            Record::failed(3, 1, "03".to_string()),
+            record(4, 3000),
+        ];
+        let slow: Vec<u8> = slow_parts(&records, Duration::from_secs(1))
+            .iter()
+            .map(|r| r.day)
+            .collect();
+        assert_eq!(slow, vec![4, 2]);
+    }
+}
@@ -0,0 +1,62 @@
+use input::{parse_puzzle, Puzzle, SolveError};
+
+/// A registered day, parsing its input into a puzzle ready to be solved
+pub struct Day {
+    pub day: u8,
+    pub parse: fn(&str) -> Result<Box<dyn Puzzle>, SolveError>,
+}
+
+pub const DAYS: [Day; 12] = [
+    Day {
+        day: 1,
+        parse: parse_puzzle::<day01::Day01>,
+    },
+    Day {
+        day: 2,
+        parse: parse_puzzle::<day02::Day02>,
+    },
+    Day {
+        day: 3,
+        parse: parse_puzzle::<day03::Day03>,
+    },
+    Day {
+        day: 4,
+        parse: parse_puzzle::<day04::Day04>,
+    },
+    Day {
+        day: 5,
+        parse: parse_puzzle::<day05::Day05>,
+    },
+    Day {
+        day: 6,
+        parse: parse_puzzle::<day06::Day06>,
+    },
+    Day {
+        day: 7,
+        parse: parse_puzzle::<day07::Day07>,
+    },
+    Day {
+        day: 8,
+        parse: parse_puzzle::<day08::Day08>,
+    },
+    Day {
+        day: 9,
+        parse: parse_puzzle::<day09::Day09>,
+    },
+    Day {
+        day: 10,
+        parse: parse_puzzle::<day10::Day10>,
+    },
+    Day {
+        day: 11,
+        parse: parse_puzzle::<day11::Day11>,
+    },
+    Day {
+        day: 12,
+        parse: parse_puzzle::<day12::Day12>,
+    },
+];
+
+pub fn find_day(day: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.day == day)
+}
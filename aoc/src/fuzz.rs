@@ -0,0 +1,382 @@
+use std::collections::hash_map::DefaultHasher;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::io;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::PathBuf;
+use std::sync::{mpsc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use generator::Rng;
+use input::data_dir;
+
+use crate::days::Day;
+
+/// Fragments that tend to upset parsers: separators, markers of the puzzles,
+/// huge numbers and characters that are numeric but not ascii digits
+const TOKENS: [&str; 20] = [
+    "\n",
+    "\r\n",
+    "\n\n",
+    " ",
+    "\t",
+    ",",
+    ":",
+    "|",
+    "-",
+    "0",
+    "9",
+    "99999999999999999999",
+    "mul(",
+    ")",
+    "do()",
+    "don't()",
+    "#",
+    "^",
+    "é",
+    "²",
+];
+
+/// Sizes passed to the generators for the seed inputs
+const SEED_SIZES: [usize; 3] = [1, 3, 8];
+
+static LAST_PANIC: Mutex<Option<String>> = Mutex::new(None);
+
+#[derive(Debug, Clone)]
+pub struct FuzzOptions {
+    pub iterations: usize,
+    pub seed: u64,
+    /// Mutated inputs are cut to this many bytes
+    pub max_len: usize,
+    /// Time allowed to parse and solve both parts of a single input
+    pub timeout: Duration,
+}
+
+/// Outcome of running a day on one input
+#[derive(Debug, Clone, PartialEq)]
+pub enum Outcome {
+    /// Solved, or rejected with an error
+    Ok,
+    /// Panicked, with the location and message of the panic
+    Crash(String),
+    /// Still running when the timeout expired
+    Timeout,
+}
+
+/// A panic and the smallest input found to trigger it
+#[derive(Debug, Clone, PartialEq)]
+pub struct Crash {
+    pub message: String,
+    pub input: String,
+}
+
+#[derive(Debug, Default)]
+pub struct FuzzReport {
+    pub runs: usize,
+    /// One entry per panic location
+    pub crashes: Vec<Crash>,
+    /// Input that ran out of time, fuzzing the day stops there
+    pub timeout: Option<String>,
+}
+
+/// Keeps panics off stderr while fuzzing, the message is reported with the crash instead
+pub fn silence_panics() {
+    panic::set_hook(Box::new(|info| {
+        let location = info.location().map(|l| l.to_string()).unwrap_or_default();
+        let message = info.payload_as_str().unwrap_or("Box<dyn Any>");
+        *LAST_PANIC.lock().unwrap() = Some(format!("{}: {}", location, message));
+    }));
+}
+
+/// Parses the input and solves both parts on a separate thread
+pub fn run_case(day: &'static Day, input: &str, timeout: Duration) -> Outcome {
+    let (sender, receiver) = mpsc::channel();
+    let input = input.to_string();
+
+    thread::spawn(move || {
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            // Errors are the expected answer to bad input, only panics count
+            if let Ok(puzzle) = (day.parse)(input.as_str()) {
+                let _ = puzzle.part(1);
+                let _ = puzzle.part(2);
+            }
+        }));
+        let outcome = match result {
+            Ok(()) => Outcome::Ok,
+            Err(payload) => {
+                let hooked = LAST_PANIC.lock().unwrap().take();
+                let message = hooked.unwrap_or_else(|| {
+                    let message = payload
+                        .downcast_ref::<&str>()
+                        .map(|s| s.to_string())
+                        .or_else(|| payload.downcast_ref::<String>().cloned());
+                    message.unwrap_or_else(|| "Box<dyn Any>".to_string())
+                });
+                Outcome::Crash(message)
+            }
+        };
+        // The receiver is gone after a timeout
+        let _ = sender.send(outcome);
+    });
+
+    receiver.recv_timeout(timeout).unwrap_or(Outcome::Timeout)
+}
+
+/// Samples and small generated inputs to start mutating from
+pub fn seed_corpus(day: u8) -> Vec<String> {
+    let mut corpus = vec![String::new()];
+
+    let prefix = format!("day{:02}-", day);
+    if let Ok(entries) = fs::read_dir(data_dir()) {
+        let mut paths: Vec<PathBuf> = entries
+            .flatten()
+            .map(|e| e.path())
+            .filter(|p| {
+                p.file_name()
+                    .and_then(|n| n.to_str())
+                    .is_some_and(|n| n.starts_with(&prefix) && n.ends_with(".txt"))
+            })
+            .collect();
+        paths.sort();
+        corpus.extend(paths.iter().filter_map(|p| fs::read_to_string(p).ok()));
+    }
+
+    for (k, size) in SEED_SIZES.iter().enumerate() {
+        corpus.extend(generator::generate(day, k as u64, *size));
+    }
+    corpus
+}
+
+/// Applies a few random edits to the input, keeping it valid UTF-8
+pub fn mutate(rng: &mut Rng, input: &str, corpus: &[String], max_len: usize) -> String {
+    let mut chars: Vec<char> = input.chars().collect();
+
+    for _ in 0..rng.range(1, 5) {
+        let at = rng.below(chars.len() as u64 + 1) as usize;
+        match rng.below(6) {
+            // Replace a character
+            0 if at < chars.len() => {
+                chars[at] = rng.pick(&TOKENS).chars().next().unwrap();
+            }
+            // Insert a token
+            1 => {
+                let token = rng.pick(&TOKENS);
+                chars.splice(at..at, token.chars());
+            }
+            // Remove a run of characters
+            2 => {
+                let end = (at + rng.range(1, 9) as usize).min(chars.len());
+                chars.drain(at..end);
+            }
+            // Repeat a run of characters
+            3 => {
+                let end = (at + rng.range(1, 17) as usize).min(chars.len());
+                let run: Vec<char> = chars[at..end].to_vec();
+                chars.splice(at..at, run);
+            }
+            // Splice in a line of another input
+            4 => {
+                let other = rng.pick(corpus);
+                let lines: Vec<&str> = other.lines().collect();
+                if !lines.is_empty() {
+                    let line = format!("{}\n", rng.pick(&lines));
+                    chars.splice(at..at, line.chars());
+                }
+            }
+            // Cut the input short
+            _ => chars.truncate(at),
+        }
+    }
+
+    let mut result: String = chars.into_iter().collect();
+    while result.len() > max_len {
+        result.pop();
+    }
+    result
+}
+
+/// The location part of a crash message, ie: `day03/src/lib.rs:199:41`
+fn crash_site(message: &str) -> &str {
+    message.split(": ").next().unwrap_or(message)
+}
+
+/// Removes characters from the input for as long as it still crashes at the same spot
+pub fn minimize(day: &'static Day, input: &str, message: &str, timeout: Duration) -> String {
+    let site = crash_site(message);
+    let same_crash = |candidate: &[char]| {
+        let candidate: String = candidate.iter().collect();
+        matches!(run_case(day, &candidate, timeout), Outcome::Crash(m) if crash_site(&m) == site)
+    };
+
+    let mut chars: Vec<char> = input.chars().collect();
+    let mut chunk = chars.len().div_ceil(2).max(1);
+    loop {
+        let mut start = 0;
+        while start < chars.len() {
+            let end = (start + chunk).min(chars.len());
+            let mut candidate = chars.clone();
+            candidate.drain(start..end);
+            if same_crash(&candidate) {
+                chars = candidate;
+            } else {
+                start += chunk;
+            }
+        }
+        if chunk == 1 {
+            break;
+        }
+        chunk = chunk.div_ceil(2);
+    }
+    chars.into_iter().collect()
+}
+
+/// Runs mutated inputs through a day, collecting the crashes with minimized inputs
+pub fn fuzz_day(day: &'static Day, options: &FuzzOptions) -> FuzzReport {
+    let corpus = seed_corpus(day.day);
+    let mut rng = Rng::new(options.seed ^ day.day as u64);
+    let mut report = FuzzReport::default();
+
+    for k in 0..options.iterations {
+        // Seeds go through unchanged first, they must not crash either
+        let input = match corpus.get(k) {
+            Some(seed) => seed.clone(),
+            None => {
+                let base = rng.pick(&corpus).clone();
+                mutate(&mut rng, &base, &corpus, options.max_len)
+            }
+        };
+        report.runs += 1;
+
+        match run_case(day, &input, options.timeout) {
+            Outcome::Ok => {}
+            Outcome::Crash(message) => {
+                let known = report
+                    .crashes
+                    .iter_mut()
+                    .find(|c| crash_site(&c.message) == crash_site(&message));
+                match known {
+                    Some(crash) if crash.input.len() > input.len() => crash.input = input,
+                    Some(_) => {}
+                    None => report.crashes.push(Crash { message, input }),
+                }
+            }
+            Outcome::Timeout => {
+                // The solver can't be stopped, carrying on would only compete with it
+                report.timeout = Some(input);
+                break;
+            }
+        }
+    }
+
+    for crash in report.crashes.iter_mut() {
+        crash.input = minimize(day, &crash.input, &crash.message, options.timeout);
+    }
+    report
+}
+
+pub fn fuzz_dir() -> PathBuf {
+    data_dir().join("fuzz")
+}
+
+/// Saves an input found by the fuzzer, ie: `data/fuzz/day03-crash-<hash>.txt`
+pub fn save_finding(day: u8, kind: &str, input: &str) -> io::Result<PathBuf> {
+    let mut hasher = DefaultHasher::new();
+    input.hash(&mut hasher);
+
+    let dir = fuzz_dir();
+    fs::create_dir_all(&dir)?;
+    let path = dir.join(format!(
+        "day{:02}-{}-{:016x}.txt",
+        day,
+        kind,
+        hasher.finish()
+    ));
+    fs::write(&path, input)?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use input::{parse_puzzle, Answer, Solution, SolveError};
+
+    use super::*;
+
+    /// Panics on `!`, loops forever on `~`
+    struct Fragile;
+
+    impl Solution for Fragile {
+        type Parsed = String;
+
+        fn parse(input: &str) -> Result<Self::Parsed, SolveError> {
+            if input.contains('?') {
+                return Err(SolveError::Parse("no questions".to_string()));
+            }
+            Ok(input.to_string())
+        }
+
+        fn part1(parsed: &Self::Parsed) -> Result<Answer, SolveError> {
+            assert!(!parsed.contains('!'), "bang");
+            while parsed.contains('~') {
+                thread::sleep(Duration::from_millis(10));
+            }
+            Ok(parsed.len().into())
+        }
+
+        fn part2(parsed: &Self::Parsed) -> Result<Answer, SolveError> {
+            Self::part1(parsed)
+        }
+    }
+
+    static FRAGILE: Day = Day {
+        day: 99,
+        parse: parse_puzzle::<Fragile>,
+    };
+
+    const TIMEOUT: Duration = Duration::from_secs(5);
+
+    #[test]
+    fn test_run_case() {
+        assert_eq!(run_case(&FRAGILE, "abc", TIMEOUT), Outcome::Ok);
+        assert_eq!(run_case(&FRAGILE, "a?c", TIMEOUT), Outcome::Ok);
+        assert!(matches!(
+            run_case(&FRAGILE, "a!c", TIMEOUT),
+            Outcome::Crash(m) if m.contains("bang")
+        ));
+        assert_eq!(
+            run_case(&FRAGILE, "a~c", Duration::from_millis(50)),
+            Outcome::Timeout
+        );
+    }
+
+    #[test]
+    fn test_minimize() {
+        let message = match run_case(&FRAGILE, "abc!def\nghi", TIMEOUT) {
+            Outcome::Crash(m) => m,
+            other => panic!("expected a crash, got {:?}", other),
+        };
+        assert_eq!(minimize(&FRAGILE, "abc!def\nghi", &message, TIMEOUT), "!");
+    }
+
+    #[test]
+    fn test_mutate() {
+        let corpus = vec!["1 2\n3 4\n".to_string(), "mul(2,3)".to_string()];
+        let mut rng = Rng::new(7);
+        let mutants: Vec<String> = (0..200)
+            .map(|_| mutate(&mut rng, &corpus[0], &corpus, 16))
+            .collect();
+        assert!(mutants.iter().all(|m| m.len() <= 16));
+        assert!(mutants.iter().any(|m| m != &corpus[0]));
+
+        let mut again = Rng::new(7);
+        assert_eq!(mutate(&mut again, &corpus[0], &corpus, 16), mutants[0]);
+    }
+
+    #[test]
+    fn test_crash_site() {
+        assert_eq!(
+            crash_site("day03/src/lib.rs:199:41: called `Option::unwrap()`"),
+            "day03/src/lib.rs:199:41"
+        );
+    }
+}
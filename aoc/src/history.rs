@@ -0,0 +1,261 @@
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use input::data_dir;
+
+/// Timings of one part from a past bench run
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    /// Seconds since the unix epoch
+    pub timestamp: u64,
+    pub day: u8,
+    pub part: u8,
+    pub input: String,
+    pub runs: usize,
+    pub median: Duration,
+    pub min: Duration,
+}
+
+impl Entry {
+    pub fn parse(line: &str) -> Result<Self, String> {
+        let columns: Vec<&str> = line.split(',').collect();
+        let [timestamp, day, part, input, runs, median, min] = columns[..] else {
+            return Err(format!("expected 7 columns, found {}", columns.len()));
+        };
+        let number = |name: &str, value: &str| -> Result<u64, String> {
+            value
+                .parse()
+                .map_err(|_| format!("invalid {}: `{}`", name, value))
+        };
+        let small = |name: &str, value: &str| -> Result<u8, String> {
+            u8::try_from(number(name, value)?)
+                .map_err(|_| format!("{} `{}` is out of range", name, value))
+        };
+
+        Ok(Self {
+            timestamp: number("timestamp", timestamp)?,
+            day: small("day", day)?,
+            part: small("part", part)?,
+            input: input.to_string(),
+            runs: usize::try_from(number("runs", runs)?)
+                .map_err(|_| format!("runs `{}` is out of range", runs))?,
+            median: Duration::from_nanos(number("median", median)?),
+            min: Duration::from_nanos(number("min", min)?),
+        })
+    }
+
+    pub fn to_line(&self) -> String {
+        format!(
+            "{},{},{},{},{},{},{}",
+            self.timestamp,
+            self.day,
+            self.part,
+            self.input,
+            self.runs,
+            self.median.as_nanos(),
+            self.min.as_nanos()
+        )
+    }
+
+    fn same_part(&self, other: &Entry) -> bool {
+        self.day == other.day && self.part == other.part && self.input == other.input
+    }
+}
+
+/// Past bench runs, oldest first
+#[derive(Debug, Default)]
+pub struct History {
+    entries: Vec<Entry>,
+}
+
+pub const HEADER: &str = "timestamp,day,part,input,runs,median_ns,min_ns";
+
+/// Where the bench history is kept, next to the puzzle inputs
+pub fn history_path() -> PathBuf {
+    data_dir().join("bench-history.csv")
+}
+
+impl History {
+    pub fn parse(data: &str) -> Result<Self, String> {
+        let mut entries: Vec<Entry> = Vec::new();
+        for (k, line) in data.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line == HEADER {
+                continue;
+            }
+            let entry = Entry::parse(line).map_err(|err| format!("Line {}: {}", k + 1, err))?;
+            entries.push(entry);
+        }
+        Ok(Self { entries })
+    }
+
+    /// Reads the history, a missing file is an empty history
+    pub fn read(path: &Path) -> io::Result<Self> {
+        let data = match fs::read_to_string(path) {
+            Ok(data) => data,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(err) => return Err(err),
+        };
+        Self::parse(data.as_str()).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+
+    /// Appends the entries to the file, writing the header to a new file.
+    ///
+    /// Input names with a comma or a line break would break the columns, nothing is written
+    /// when an entry has one.
+    pub fn append(path: &Path, entries: &[Entry]) -> io::Result<()> {
+        if let Some(entry) = entries.iter().find(|e| e.input.contains([',', '\n', '\r'])) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "input name `{}` can't be recorded, it has a comma or a line break",
+                    entry.input
+                ),
+            ));
+        }
+        let is_new = !path.exists();
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        if is_new {
+            writeln!(file, "{}", HEADER)?;
+        }
+        for entry in entries.iter() {
+            writeln!(file, "{}", entry.to_line())?;
+        }
+        Ok(())
+    }
+
+    /// The most recent entry for the same part and input
+    pub fn previous(&self, entry: &Entry) -> Option<&Entry> {
+        self.entries.iter().rev().find(|e| e.same_part(entry))
+    }
+
+    /// The entry with the lowest median for the same part and input
+    pub fn best(&self, entry: &Entry) -> Option<&Entry> {
+        self.entries
+            .iter()
+            .filter(|e| e.same_part(entry))
+            .min_by_key(|e| e.median)
+    }
+
+    /// Compares a new entry with the history, flagging a median slower than
+    /// the previous one by more than `threshold` percent
+    pub fn compare(&self, entry: &Entry, threshold: f64) -> Comparison {
+        let previous = self.previous(entry).map(|e| e.median);
+        let best = self.best(entry).map(|e| e.median);
+        let change = previous.map(|p| change_percent(p, entry.median));
+        Comparison {
+            previous,
+            best,
+            change,
+            regressed: change.is_some_and(|c| c > threshold),
+        }
+    }
+}
+
+/// How a new entry relates to the past runs of the same part
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison {
+    pub previous: Option<Duration>,
+    pub best: Option<Duration>,
+    /// Change of the median over the previous run in percent, positive is slower
+    pub change: Option<f64>,
+    pub regressed: bool,
+}
+
+pub fn change_percent(old: Duration, new: Duration) -> f64 {
+    if old.is_zero() {
+        return 0.0;
+    }
+    (new.as_nanos() as f64 - old.as_nanos() as f64) / old.as_nanos() as f64 * 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(timestamp: u64, day: u8, median_micros: u64) -> Entry {
+        Entry {
+            timestamp,
+            day,
+            part: 1,
+            input: format!("{:02}", day),
+            runs: 20,
+            median: Duration::from_micros(median_micros),
+            min: Duration::from_micros(median_micros - 1),
+        }
+    }
+
+    #[test]
+    fn test_entry_line() {
+        let e = entry(1733000000, 7, 40);
+        assert_eq!(e.to_line(), "1733000000,7,1,07,20,40000,39000".to_string());
+        assert_eq!(Entry::parse(e.to_line().as_str()), Ok(e));
+        assert!(Entry::parse("1,2,3").is_err());
+        assert!(Entry::parse("1,2,1,02,20,fast,1").is_err());
+        assert_eq!(
+            Entry::parse("1,300,1,02,20,1,1"),
+            Err("day `300` is out of range".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_history() {
+        let data = format!(
+            "{}\n{}\n\n{}\n",
+            HEADER,
+            entry(1, 7, 40).to_line(),
+            entry(2, 7, 50).to_line()
+        );
+        let history = History::parse(data.as_str()).unwrap();
+        assert_eq!(history.entries.len(), 2);
+        assert_eq!(
+            History::parse("nope").unwrap_err(),
+            "Line 1: expected 7 columns, found 1".to_string()
+        );
+    }
+
+    #[test]
+    fn test_compare() {
+        let history = History {
+            entries: vec![entry(1, 7, 40), entry(2, 7, 50), entry(3, 8, 10)],
+        };
+
+        let slower = history.compare(&entry(4, 7, 60), 10.0);
+        assert_eq!(slower.previous, Some(Duration::from_micros(50)));
+        assert_eq!(slower.best, Some(Duration::from_micros(40)));
+        assert_eq!(slower.change, Some(20.0));
+        assert!(slower.regressed);
+
+        let noise = history.compare(&entry(4, 7, 54), 10.0);
+        assert!(!noise.regressed);
+
+        let new_day = history.compare(&entry(4, 9, 54), 10.0);
+        assert_eq!(new_day.previous, None);
+        assert!(!new_day.regressed);
+    }
+
+    #[test]
+    fn test_append() {
+        let path = std::env::temp_dir().join(format!("aoc-history-{}.csv", std::process::id()));
+        let _ = fs::remove_file(&path);
+
+        History::append(&path, &[entry(1, 7, 40)]).unwrap();
+        History::append(&path, &[entry(2, 7, 50), entry(2, 8, 10)]).unwrap();
+        let history = History::read(&path).unwrap();
+        assert_eq!(history.entries.len(), 3);
+        assert_eq!(
+            fs::read_to_string(&path).unwrap().lines().next(),
+            Some(HEADER)
+        );
+
+        let mut comma = entry(3, 7, 40);
+        comma.input = "a,b.txt".to_string();
+        let err = History::append(&path, &[entry(3, 8, 10), comma]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        assert_eq!(History::read(&path).unwrap().entries.len(), 3);
+
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,188 @@
+pub mod alloc;
+pub mod budget;
+pub mod days;
+pub mod fuzz;
+pub mod history;
+pub mod report;
+pub mod scaffold;
+pub mod selector;
+pub mod stats;
+pub mod summary;
+pub mod verify;
+
+use std::fmt;
+use std::io;
+use std::time::Instant;
+
+use alloc::AllocStats;
+use days::Day;
+use input::{Answer, Puzzle, SolveError, Stopwatch};
+use rayon::prelude::*;
+use selector::InputSelector;
+use stats::Stats;
+
+#[cfg(feature = "alloc-stats")]
+#[global_allocator]
+static ALLOC: alloc::CountingAlloc = alloc::CountingAlloc;
+
+/// Reason a part could not produce an answer
+#[derive(Debug)]
+pub enum RunError {
+    /// The input could not be read
+    Io(io::Error),
+    /// The input was read but the puzzle rejected it
+    Solve(SolveError),
+}
+
+impl fmt::Display for RunError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RunError::Io(err) => write!(f, "unable to read input: {}", err),
+            RunError::Solve(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for RunError {}
+
+impl From<io::Error> for RunError {
+    fn from(err: io::Error) -> Self {
+        RunError::Io(err)
+    }
+}
+
+impl From<SolveError> for RunError {
+    fn from(err: SolveError) -> Self {
+        RunError::Solve(err)
+    }
+}
+
+/// Outcome of running a single part of a day
+#[derive(Debug)]
+pub struct PartRun {
+    pub day: u8,
+    pub part: u8,
+    pub input: String,
+    pub answer: Answer,
+    /// Read, parse and solve phases of the run
+    pub timings: Stopwatch,
+    /// Heap activity of parse and solve, with the `alloc-stats` feature
+    pub allocs: Option<AllocStats>,
+}
+
+/// A day's input read and parsed once, ready to solve any of its parts
+pub struct ParsedDay {
+    day: u8,
+    input: String,
+    puzzle: Box<dyn Puzzle>,
+    /// Read and parse phases, reported with the first part of a run
+    shared: (Stopwatch, AllocStats),
+}
+
+impl ParsedDay {
+    pub fn new(day: &Day, selector: &InputSelector) -> Result<Self, RunError> {
+        let mut timings = Stopwatch::new();
+        let input_string = timings.time("read", || selector.read(day.day))?;
+        let (puzzle, allocs) =
+            alloc::measure(|| timings.time("parse", || (day.parse)(input_string.as_str())));
+
+        Ok(Self {
+            day: day.day,
+            input: selector.name(day.day),
+            puzzle: puzzle?,
+            shared: (timings, allocs),
+        })
+    }
+
+    /// Solves a part of the parsed puzzle.
+    ///
+    /// With `report_parse` the run also reports the read and parse costs, so that the first part
+    /// of a run carries them and the parts after it only time their own solve.
+    pub fn solve(&self, part: u8, report_parse: bool) -> Result<PartRun, RunError> {
+        let (mut timings, shared_allocs) = if report_parse {
+            self.shared.clone()
+        } else {
+            Default::default()
+        };
+        let (answer, allocs) = alloc::measure(|| timings.time("solve", || self.puzzle.part(part)));
+        let answer = answer?;
+
+        Ok(PartRun {
+            day: self.day,
+            part,
+            input: self.input.clone(),
+            answer,
+            timings,
+            allocs: alloc::enabled().then_some(shared_allocs.then(allocs)),
+        })
+    }
+}
+
+pub fn run_part(day: &Day, part: u8, selector: &InputSelector) -> Result<PartRun, RunError> {
+    ParsedDay::new(day, selector)?.solve(part, true)
+}
+
+/// Runs the parts of a day against a single parse of its input, concurrently on the rayon pool
+/// with `parallel`.
+///
+/// A read or parse error fails every part, so it comes back once for the whole day.
+pub fn run_parts(
+    day: &Day,
+    parts: &[u8],
+    selector: &InputSelector,
+    parallel: bool,
+) -> Result<Vec<Result<PartRun, RunError>>, RunError> {
+    let parsed = ParsedDay::new(day, selector)?;
+    let solve = |(k, part): (usize, &u8)| parsed.solve(*part, k == 0);
+    if parallel {
+        Ok(parts.par_iter().enumerate().map(solve).collect())
+    } else {
+        Ok(parts.iter().enumerate().map(solve).collect())
+    }
+}
+
+/// Outcome of running a single part repeatedly
+#[derive(Debug)]
+pub struct RepeatRun {
+    pub day: u8,
+    pub part: u8,
+    pub input: String,
+    pub answer: Answer,
+    /// Parse and solve durations of the measured runs
+    pub stats: Stats,
+}
+
+/// Runs a part `warmup` times unmeasured, then `repeat` times measured.
+///
+/// The input is read once up front so file I/O stays out of the measurements.
+pub fn repeat_part(
+    day: &Day,
+    part: u8,
+    selector: &InputSelector,
+    repeat: usize,
+    warmup: usize,
+) -> Result<RepeatRun, RunError> {
+    let input_string = selector.read(day.day)?;
+    let run_once = || (day.parse)(input_string.as_str())?.part(part);
+
+    for _ in 0..warmup {
+        run_once()?;
+    }
+
+    let mut answer = None;
+    let mut samples = Vec::with_capacity(repeat);
+    for _ in 0..repeat.max(1) {
+        let ts = Instant::now();
+        let result = run_once()?;
+        samples.push(ts.elapsed());
+        answer = Some(result);
+    }
+
+    Ok(RepeatRun {
+        day: day.day,
+        part,
+        input: selector.name(day.day),
+        answer: answer.expect("At least one run"),
+        stats: Stats::from_samples(&samples).expect("At least one sample"),
+    })
+}
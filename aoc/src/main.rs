@@ -0,0 +1,584 @@
+use std::process::ExitCode;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use aoc::budget::{parse_seconds, run_parts_within, slow_parts};
+use aoc::days::{find_day, Day, DAYS};
+use aoc::fuzz::{fuzz_day, save_finding, silence_panics, FuzzOptions};
+use aoc::history::{history_path, Entry, History};
+use aoc::report::{Format, Record};
+use aoc::scaffold::scaffold_day;
+use aoc::selector::InputSelector;
+use aoc::summary::summary_table;
+use aoc::verify::{verify_all, Status};
+use aoc::{repeat_part, run_parts, PartRun, RunError};
+use clap::{Args, Parser, Subcommand};
+use input::{format_duration, read_answers};
+use rayon::prelude::*;
+use tracing_subscriber::EnvFilter;
+
+/// Runs the Advent of Code 2024 solutions
+#[derive(Debug, Parser)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    #[command(flatten)]
+    run: RunArgs,
+
+    /// Solver diagnostics to print, ie: `debug` or `day12=trace`, overrides `RUST_LOG`
+    #[arg(long, global = true)]
+    log_level: Option<String>,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Checks every day against the answers recorded in data/answers.txt
+    Verify,
+    /// Times every part, appends the results to data/bench-history.csv and
+    /// compares them with the previous and best runs
+    Bench(BenchArgs),
+    /// Feeds mutated inputs to the parsers and solvers, saving the ones that panic or hang
+    /// to data/fuzz
+    Fuzz(FuzzArgs),
+    /// Creates the crate for a new day from the day00 template
+    New {
+        /// Day to create
+        day: u8,
+    },
+}
+
+#[derive(Debug, Args)]
+struct BenchArgs {
+    /// Day to bench, benches all days when omitted
+    #[arg(short, long)]
+    day: Option<u8>,
+
+    /// Input to use: `day` for the puzzle input, a sample name like `sample2` or a file path
+    #[arg(short, long, default_value = "day")]
+    input: InputSelector,
+
+    /// Measured runs of each part
+    #[arg(short, long, default_value_t = 20, value_parser = clap::value_parser!(u32).range(1..))]
+    repeat: u32,
+
+    /// Unmeasured runs before the measured ones
+    #[arg(long, default_value_t = 3)]
+    warmup: u32,
+
+    /// Percentage over the previous median that counts as a regression
+    #[arg(short, long, default_value_t = 10.0)]
+    threshold: f64,
+
+    /// Compare without appending the results to the history
+    #[arg(long)]
+    dry_run: bool,
+}
+
+#[derive(Debug, Args)]
+struct FuzzArgs {
+    /// Day to fuzz, fuzzes all days when omitted
+    #[arg(short, long)]
+    day: Option<u8>,
+
+    /// Inputs to try for each day
+    #[arg(short = 'n', long, default_value_t = 10_000)]
+    iterations: usize,
+
+    /// Seed of the mutations, the same seed tries the same inputs
+    #[arg(short, long, default_value_t = 1)]
+    seed: u64,
+
+    /// Longest input to try, in bytes
+    #[arg(long, default_value_t = 512)]
+    max_len: usize,
+
+    /// Seconds an input may run before it is reported as a hang
+    #[arg(long, value_parser = parse_seconds, default_value = "2")]
+    timeout: Duration,
+}
+
+#[derive(Debug, Args)]
+struct RunArgs {
+    /// Day to run
+    #[arg(short, long, required_unless_present = "all", conflicts_with = "all")]
+    day: Option<u8>,
+
+    /// Part to run, runs both parts when omitted
+    #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+
+    /// Run all days
+    #[arg(short, long)]
+    all: bool,
+
+    /// Input to use: `day` for the puzzle input, a sample name like `sample2` or a file path
+    #[arg(short, long, default_value = "day")]
+    input: InputSelector,
+
+    /// Output format of the results
+    #[arg(short, long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+
+    /// Run each part N times and report timing stats instead of a single run
+    #[arg(short, long, value_parser = clap::value_parser!(u32).range(1..), conflicts_with = "format")]
+    repeat: Option<u32>,
+
+    /// Run the parts concurrently and print a summary table
+    #[arg(long, conflicts_with = "repeat")]
+    parallel: bool,
+
+    /// Seconds each day gets to solve its parts, parts still running are reported as timed out
+    #[arg(long, value_parser = parse_seconds, conflicts_with = "repeat")]
+    budget: Option<Duration>,
+
+    /// Seconds after which a part is listed as slow
+    #[arg(long, value_parser = parse_seconds, default_value = "1")]
+    slow: Duration,
+
+    /// Unmeasured runs before the measured ones when repeating
+    #[arg(long, default_value_t = 3, requires = "repeat")]
+    warmup: u32,
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    init_tracing(cli.log_level.as_deref());
+
+    match cli.command {
+        Some(Command::Verify) => verify(),
+        Some(Command::Bench(args)) => bench(&args),
+        Some(Command::Fuzz(args)) => fuzz(&args),
+        Some(Command::New { day }) => new_day(day),
+        None => run(&cli.run),
+    }
+}
+
+/// Diagnostics go to stderr and are off unless asked for
+fn init_tracing(log_level: Option<&str>) {
+    let filter = match log_level {
+        Some(level) => EnvFilter::new(level),
+        None => EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("warn")),
+    };
+    tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(std::io::stderr)
+        .init();
+}
+
+/// The registered day asked for, or every day when none is
+fn select_days(day: Option<u8>) -> Result<Vec<&'static Day>, ExitCode> {
+    match day {
+        Some(day) => match find_day(day) {
+            Some(d) => Ok(vec![d]),
+            None => {
+                eprintln!("Day {} is not solved yet", day);
+                Err(ExitCode::from(2))
+            }
+        },
+        None => Ok(DAYS.iter().collect()),
+    }
+}
+
+fn run(args: &RunArgs) -> ExitCode {
+    let days = match select_days(args.day) {
+        Ok(days) => days,
+        Err(code) => return code,
+    };
+    let parts: Vec<u8> = match args.part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    };
+
+    if let Some(repeat) = args.repeat {
+        return run_repeated(args, &days, &parts, repeat as usize);
+    }
+
+    // The parts of a day share one parse of its input, with `--parallel` they are then solved
+    // concurrently too
+    let ts = Instant::now();
+    let mut records: Vec<Record> = if args.parallel {
+        days.par_iter()
+            .flat_map(|day| day_records(day, &parts, &args.input, args.budget, true))
+            .collect()
+    } else {
+        days.iter()
+            .flat_map(|day| day_records(day, &parts, &args.input, args.budget, false))
+            .collect()
+    };
+    let wall_clock = ts.elapsed();
+    records.sort_by_key(|r| (r.day, r.part));
+
+    match args.format {
+        Format::Text if args.parallel => {
+            println!("{}", summary_table(&records, wall_clock, 3));
+            print_slow_parts(&records, args.slow);
+        }
+        Format::Text => {
+            for record in records.iter().filter(|r| r.status == "ok") {
+                println!("{}", record.to_text());
+            }
+            print_slow_parts(&records, args.slow);
+        }
+        Format::Json => {
+            for record in records.iter() {
+                println!("{}", record.to_json());
+            }
+        }
+        Format::Csv => {
+            println!("{}", Record::csv_header());
+            for record in records.iter() {
+                println!("{}", record.to_csv());
+            }
+        }
+    }
+
+    match records.iter().find(|r| r.status != "ok") {
+        Some(record) => exit_code(record.status),
+        None => ExitCode::SUCCESS,
+    }
+}
+
+/// Exit code for a failed part, so scripts can tell bad input from other failures
+fn exit_code(status: &str) -> ExitCode {
+    match status {
+        "parse_error" => ExitCode::from(3),
+        "invalid_input" => ExitCode::from(4),
+        "unsolvable" => ExitCode::from(5),
+        "overflow" => ExitCode::from(6),
+        _ => ExitCode::FAILURE,
+    }
+}
+
+/// Records of the parts of a day, solved against a single parse of its input
+fn day_records(
+    day: &'static Day,
+    parts: &[u8],
+    input: &InputSelector,
+    budget: Option<Duration>,
+    parallel: bool,
+) -> Vec<Record> {
+    let runs: Result<Vec<Option<Result<PartRun, RunError>>>, RunError> = match budget {
+        Some(budget) => run_parts_within(day, parts, input, budget, parallel),
+        None => {
+            run_parts(day, parts, input, parallel).map(|runs| runs.into_iter().map(Some).collect())
+        }
+    };
+
+    match runs {
+        Ok(runs) => parts
+            .iter()
+            .zip(runs)
+            .map(|(part, run)| match run {
+                Some(result) => part_record(day, *part, input, result),
+                None => {
+                    eprintln!(
+                        "Day {:02} part {} [{}]: aborted after exceeding the day's budget of {}",
+                        day.day,
+                        part,
+                        input.name(day.day),
+                        format_duration(budget.unwrap_or_default())
+                    );
+                    Record::timed_out(day.day, *part, input.name(day.day))
+                }
+            })
+            .collect(),
+        Err(err) => {
+            // Reading or parsing failed, no part could run
+            eprintln!("Day {:02} [{}]: {}", day.day, input.name(day.day), err);
+            parts
+                .iter()
+                .map(|part| Record::from_error(day.day, *part, input.name(day.day), &err))
+                .collect()
+        }
+    }
+}
+
+fn part_record(
+    day: &'static Day,
+    part: u8,
+    input: &InputSelector,
+    result: Result<PartRun, RunError>,
+) -> Record {
+    match result {
+        Ok(run) => Record::from_run(&run),
+        Err(err) => {
+            eprintln!(
+                "Day {:02} part {} [{}]: {}",
+                day.day,
+                part,
+                input.name(day.day),
+                err
+            );
+            Record::from_error(day.day, part, input.name(day.day), &err)
+        }
+    }
+}
+
+fn print_slow_parts(records: &[Record], threshold: Duration) {
+    let slow = slow_parts(records, threshold);
+    if slow.is_empty() {
+        return;
+    }
+
+    println!("Slow parts (over {}):", format_duration(threshold));
+    for record in slow.iter() {
+        println!(
+            "  Day {:02} part {}: {}",
+            record.day,
+            record.part,
+            format_duration(record.duration().unwrap_or_default())
+        );
+    }
+}
+
+fn run_repeated(args: &RunArgs, days: &[&Day], parts: &[u8], repeat: usize) -> ExitCode {
+    let mut failed = false;
+
+    for day in days.iter() {
+        for part in parts.iter() {
+            let label = format!(
+                "Day {:02} part {} [{}]",
+                day.day,
+                part,
+                args.input.name(day.day)
+            );
+            match repeat_part(day, *part, &args.input, repeat, args.warmup as usize) {
+                Ok(run) => println!("{}: {}, {}", label, run.answer, run.stats),
+                Err(err) => {
+                    eprintln!("{}: {}", label, err);
+                    failed = true;
+                }
+            }
+        }
+    }
+
+    if failed {
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
+}
+
+fn verify() -> ExitCode {
+    let answers = match read_answers() {
+        Ok(answers) => answers,
+        Err(err) => {
+            eprintln!("Unable to read recorded answers: {}", err);
+            return ExitCode::from(2);
+        }
+    };
+
+    let results = verify_all(&answers);
+
+    println!(
+        "{:<4} {:<4} {:<20} {:<20} Status",
+        "Day", "Part", "Expected", "Actual"
+    );
+    for item in results.iter() {
+        let expected = item.expected.as_deref().unwrap_or("-");
+        let actual = match item.actual.as_ref() {
+            Some(answer) => answer.to_string(),
+            None => "-".to_string(),
+        };
+        let status = match &item.status {
+            Status::Pass => "pass".to_string(),
+            Status::Fail => "FAIL".to_string(),
+            Status::Missing => "MISSING".to_string(),
+            Status::Error(err) => format!("ERROR: {}", err),
+        };
+        println!(
+            "{:<4} {:<4} {:<20} {:<20} {}",
+            format!("{:02}", item.day),
+            item.part,
+            expected,
+            actual,
+            status
+        );
+    }
+
+    let passed = results.iter().filter(|r| r.passed()).count();
+    println!("{}/{} parts verified", passed, results.len());
+
+    if passed < results.len() {
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
+}
+
+fn bench(args: &BenchArgs) -> ExitCode {
+    let days = match select_days(args.day) {
+        Ok(days) => days,
+        Err(code) => return code,
+    };
+
+    let path = history_path();
+    let history = match History::read(&path) {
+        Ok(history) => history,
+        Err(err) => {
+            eprintln!("Unable to read {}: {}", path.display(), err);
+            return ExitCode::from(2);
+        }
+    };
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+
+    println!(
+        "{:<4} {:<4} {:>10} {:>10} {:>8} {:>10} Status",
+        "Day", "Part", "Median", "Previous", "Change", "Best"
+    );
+
+    let mut entries: Vec<Entry> = Vec::new();
+    let mut regressions = 0;
+    for day in days.iter() {
+        for part in 1..=2 {
+            let run = match repeat_part(
+                day,
+                part,
+                &args.input,
+                args.repeat as usize,
+                args.warmup as usize,
+            ) {
+                Ok(run) => run,
+                Err(err) => {
+                    eprintln!(
+                        "Day {:02} part {} [{}]: {}",
+                        day.day,
+                        part,
+                        args.input.name(day.day),
+                        err
+                    );
+                    continue;
+                }
+            };
+
+            let entry = Entry {
+                timestamp,
+                day: run.day,
+                part: run.part,
+                input: run.input,
+                runs: run.stats.runs,
+                median: run.stats.median,
+                min: run.stats.min,
+            };
+            let comparison = history.compare(&entry, args.threshold);
+            let optional = |d: Option<Duration>| d.map(format_duration).unwrap_or("-".to_string());
+            let change = match comparison.change {
+                Some(c) => format!("{:+.1}%", c),
+                None => "-".to_string(),
+            };
+            let status = match comparison.previous {
+                None => "new",
+                Some(_) if comparison.regressed => "REGRESSED",
+                Some(_) => "ok",
+            };
+            if comparison.regressed {
+                regressions += 1;
+            }
+
+            println!(
+                "{:<4} {:<4} {:>10} {:>10} {:>8} {:>10} {}",
+                format!("{:02}", entry.day),
+                entry.part,
+                format_duration(entry.median),
+                optional(comparison.previous),
+                change,
+                optional(comparison.best),
+                status
+            );
+            entries.push(entry);
+        }
+    }
+
+    println!(
+        "{} parts benched, {} regressed over {}%",
+        entries.len(),
+        regressions,
+        args.threshold
+    );
+
+    if !args.dry_run {
+        if let Err(err) = History::append(&path, &entries) {
+            eprintln!("Unable to write {}: {}", path.display(), err);
+            return ExitCode::from(2);
+        }
+    }
+
+    if regressions > 0 {
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
+}
+
+fn new_day(day: u8) -> ExitCode {
+    let scaffold = match scaffold_day(day) {
+        Ok(scaffold) => scaffold,
+        Err(err) => {
+            eprintln!("Unable to create day {:02}: {}", day, err);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    for path in scaffold.created.iter() {
+        println!("created {}", path.display());
+    }
+    for path in scaffold.updated.iter() {
+        println!("updated {}", path.display());
+    }
+    ExitCode::SUCCESS
+}
+
+fn fuzz(args: &FuzzArgs) -> ExitCode {
+    let days = match select_days(args.day) {
+        Ok(days) => days,
+        Err(code) => return code,
+    };
+    let options = FuzzOptions {
+        iterations: args.iterations,
+        seed: args.seed,
+        max_len: args.max_len,
+        timeout: args.timeout,
+    };
+
+    silence_panics();
+    let mut failed = false;
+    for day in days.iter() {
+        let report = fuzz_day(day, &options);
+        println!(
+            "Day {:02}: {} runs, {} crashes{}",
+            day.day,
+            report.runs,
+            report.crashes.len(),
+            if report.timeout.is_some() {
+                ", timed out"
+            } else {
+                ""
+            }
+        );
+
+        let findings = report
+            .crashes
+            .iter()
+            .map(|c| ("crash", c.message.as_str(), c.input.as_str()))
+            .chain(
+                report
+                    .timeout
+                    .as_deref()
+                    .map(|input| ("timeout", "hang", input)),
+            );
+        for (kind, message, input) in findings {
+            failed = true;
+            println!("  {}", message);
+            match save_finding(day.day, kind, input) {
+                Ok(path) => println!("    input {:?} saved to {}", input, path.display()),
+                Err(err) => eprintln!("    unable to save input {:?}: {}", input, err),
+            }
+        }
+    }
+
+    if failed {
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
+}
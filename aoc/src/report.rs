@@ -0,0 +1,298 @@
+use std::time::Duration;
+
+use clap::ValueEnum;
+use input::{format_duration, SolveError, Stopwatch};
+
+use crate::alloc::AllocStats;
+use crate::{PartRun, RunError};
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum Format {
+    /// Human readable lines
+    Text,
+    /// One JSON object per line
+    Json,
+    /// Comma separated values with a header row
+    Csv,
+}
+
+/// A single run as reported to the outside world
+#[derive(Debug)]
+pub struct Record {
+    pub day: u8,
+    pub part: u8,
+    pub input: String,
+    pub answer: Option<String>,
+    pub read: Option<Duration>,
+    pub parse: Option<Duration>,
+    pub solve: Option<Duration>,
+    pub allocs: Option<AllocStats>,
+    pub status: &'static str,
+}
+
+impl Record {
+    pub fn from_run(run: &PartRun) -> Self {
+        Self {
+            day: run.day,
+            part: run.part,
+            input: run.input.clone(),
+            answer: Some(run.answer.to_string()),
+            read: run.timings.get("read"),
+            parse: run.timings.get("parse"),
+            solve: run.timings.get("solve"),
+            allocs: run.allocs,
+            status: "ok",
+        }
+    }
+
+    /// A part that could not run, ie: when its input is missing
+    pub fn failed(day: u8, part: u8, input: String) -> Self {
+        Self {
+            day,
+            part,
+            input,
+            answer: None,
+            read: None,
+            parse: None,
+            solve: None,
+            allocs: None,
+            status: "error",
+        }
+    }
+
+    /// A part that could not run, with a status telling why
+    pub fn from_error(day: u8, part: u8, input: String, err: &RunError) -> Self {
+        let status = match err {
+            RunError::Io(_) => "error",
+            RunError::Solve(SolveError::Parse(_)) => "parse_error",
+            RunError::Solve(SolveError::Validation(_)) => "invalid_input",
+            RunError::Solve(SolveError::Unsolvable(_)) => "unsolvable",
+            RunError::Solve(SolveError::Overflow(_)) => "overflow",
+        };
+        Self {
+            status,
+            ..Self::failed(day, part, input)
+        }
+    }
+
+    /// Time spent on the part, `None` if it could not run
+    pub fn duration(&self) -> Option<Duration> {
+        self.answer.as_ref()?;
+        Some([self.read, self.parse, self.solve].iter().flatten().sum())
+    }
+
+    /// A part that ran out of its time budget
+    pub fn timed_out(day: u8, part: u8, input: String) -> Self {
+        Self {
+            status: "timeout",
+            ..Self::failed(day, part, input)
+        }
+    }
+
+    pub fn to_text(&self) -> String {
+        let label = format!("Day {:02} part {} [{}]", self.day, self.part, self.input);
+        let Some(answer) = self.answer.as_ref() else {
+            return format!("{}: {}", label, self.status);
+        };
+
+        let mut timings = Stopwatch::new();
+        for (name, duration) in [
+            ("read", self.read),
+            ("parse", self.parse),
+            ("solve", self.solve),
+        ] {
+            if let Some(d) = duration {
+                timings.record(name, d);
+            }
+        }
+        let mut text = format!(
+            "{}: {}, duration: {} ({})",
+            label,
+            answer,
+            format_duration(timings.total()),
+            timings.report()
+        );
+        if let Some(allocs) = self.allocs {
+            text.push_str(format!(", heap: ({})", allocs).as_str());
+        }
+        text
+    }
+
+    pub fn to_json(&self) -> String {
+        let answer = match self.answer.as_ref() {
+            Some(a) => json_string(a),
+            None => "null".to_string(),
+        };
+        format!(
+            "{{\"day\":{},\"part\":{},\"input\":{},\"answer\":{},\"read_ns\":{},\"parse_ns\":{},\"solve_ns\":{},\"peak_bytes\":{},\"allocations\":{},\"allocated_bytes\":{},\"status\":{}}}",
+            self.day,
+            self.part,
+            json_string(self.input.as_str()),
+            answer,
+            json_nanos(self.read),
+            json_nanos(self.parse),
+            json_nanos(self.solve),
+            json_number(self.allocs.map(|a| a.peak)),
+            json_number(self.allocs.map(|a| a.allocations)),
+            json_number(self.allocs.map(|a| a.bytes)),
+            json_string(self.status)
+        )
+    }
+
+    pub fn csv_header() -> &'static str {
+        "day,part,input,answer,read_ns,parse_ns,solve_ns,peak_bytes,allocations,allocated_bytes,status"
+    }
+
+    pub fn to_csv(&self) -> String {
+        let nanos = |d: Option<Duration>| match d {
+            Some(d) => d.as_nanos().to_string(),
+            None => "".to_string(),
+        };
+        let number = |n: Option<u64>| match n {
+            Some(n) => n.to_string(),
+            None => "".to_string(),
+        };
+        format!(
+            "{},{},{},{},{},{},{},{},{},{},{}",
+            self.day,
+            self.part,
+            csv_field(self.input.as_str()),
+            csv_field(self.answer.as_deref().unwrap_or("")),
+            nanos(self.read),
+            nanos(self.parse),
+            nanos(self.solve),
+            number(self.allocs.map(|a| a.peak)),
+            number(self.allocs.map(|a| a.allocations)),
+            number(self.allocs.map(|a| a.bytes)),
+            self.status
+        )
+    }
+}
+
+fn json_nanos(duration: Option<Duration>) -> String {
+    match duration {
+        Some(d) => d.as_nanos().to_string(),
+        None => "null".to_string(),
+    }
+}
+
+fn json_number(value: Option<u64>) -> String {
+    match value {
+        Some(n) => n.to_string(),
+        None => "null".to_string(),
+    }
+}
+
+fn json_string(value: &str) -> String {
+    let mut result = String::with_capacity(value.len() + 2);
+    result.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            c if (c as u32) < 0x20 => result.push_str(format!("\\u{:04x}", c as u32).as_str()),
+            c => result.push(c),
+        }
+    }
+    result.push('"');
+    result
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        return format!("\"{}\"", value.replace('"', "\"\""));
+    }
+    value.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_record() -> Record {
+        Record {
+            day: 7,
+            part: 1,
+            input: "07-sample".to_string(),
+            answer: Some("3749".to_string()),
+            read: Some(Duration::from_nanos(800)),
+            parse: Some(Duration::from_nanos(1500)),
+            solve: Some(Duration::from_micros(40)),
+            allocs: None,
+            status: "ok",
+        }
+    }
+
+    #[test]
+    fn test_to_json() {
+        assert_eq!(
+            sample_record().to_json(),
+            r#"{"day":7,"part":1,"input":"07-sample","answer":"3749","read_ns":800,"parse_ns":1500,"solve_ns":40000,"peak_bytes":null,"allocations":null,"allocated_bytes":null,"status":"ok"}"#
+        );
+        let failed = Record::failed(3, 2, "03".to_string());
+        assert_eq!(
+            failed.to_json(),
+            r#"{"day":3,"part":2,"input":"03","answer":null,"read_ns":null,"parse_ns":null,"solve_ns":null,"peak_bytes":null,"allocations":null,"allocated_bytes":null,"status":"error"}"#
+        );
+    }
+
+    #[test]
+    fn test_from_error() {
+        let status = |err: SolveError| {
+            Record::from_error(11, 2, "11".to_string(), &RunError::Solve(err)).status
+        };
+        assert_eq!(status(SolveError::Parse(String::new())), "parse_error");
+        assert_eq!(status(SolveError::Unsolvable(String::new())), "unsolvable");
+        assert_eq!(status(SolveError::Overflow(String::new())), "overflow");
+    }
+
+    #[test]
+    fn test_to_csv() {
+        assert_eq!(
+            sample_record().to_csv(),
+            "7,1,07-sample,3749,800,1500,40000,,,,ok".to_string()
+        );
+        let mut record = sample_record();
+        record.answer = Some("1,2,\"3\"".to_string());
+        assert_eq!(
+            record.to_csv(),
+            "7,1,07-sample,\"1,2,\"\"3\"\"\",800,1500,40000,,,,ok".to_string()
+        );
+    }
+
+    #[test]
+    fn test_to_text() {
+        assert_eq!(
+            sample_record().to_text(),
+            "Day 07 part 1 [07-sample]: 3749, duration: 42.3µs (read: 800ns, parse: 1.5µs, solve: 40µs)"
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn test_alloc_stats() {
+        let record = Record {
+            allocs: Some(AllocStats {
+                allocations: 12,
+                bytes: 4096,
+                peak: 1536,
+            }),
+            ..sample_record()
+        };
+        assert!(record
+            .to_text()
+            .ends_with(", heap: (peak: 1.5 KiB, allocations: 12, allocated: 4 KiB)"));
+        assert!(record.to_json().contains(
+            r#""peak_bytes":1536,"allocations":12,"allocated_bytes":4096,"status":"ok""#
+        ));
+        assert!(record.to_csv().ends_with(",1536,12,4096,ok"));
+    }
+
+    #[test]
+    fn test_json_string() {
+        assert_eq!(json_string("a\"b\\c\n"), r#""a\"b\\c\n""#.to_string());
+    }
+}
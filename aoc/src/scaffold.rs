@@ -0,0 +1,288 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use input::data_dir;
+
+/// Files copied from the day00 template, relative to the crate root
+const TEMPLATE_FILES: [&str; 3] = ["Cargo.toml", "src/lib.rs", "benches/benchmarks.rs"];
+
+/// What the scaffolding created or changed
+#[derive(Debug, Default)]
+pub struct Scaffold {
+    pub created: Vec<PathBuf>,
+    pub updated: Vec<PathBuf>,
+}
+
+/// The workspace root, parent of this crate
+pub fn workspace_dir() -> PathBuf {
+    let crate_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    crate_dir.parent().unwrap_or(crate_dir).to_path_buf()
+}
+
+/// Creates the crate for a new day from the day00 template.
+///
+/// Refuses to touch anything when the crate already exists. Data files
+/// that already exist are kept as is.
+pub fn scaffold_day(day: u8) -> io::Result<Scaffold> {
+    if !(1..=25).contains(&day) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("Day {} is not an Advent of Code day", day),
+        ));
+    }
+
+    let root = workspace_dir();
+    let template_dir = root.join("day00");
+    let crate_dir = root.join(format!("day{:02}", day));
+    if crate_dir.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} already exists", crate_dir.display()),
+        ));
+    }
+
+    // Read everything first so a broken template leaves nothing behind
+    let mut files: Vec<(PathBuf, String)> = Vec::new();
+    for file in TEMPLATE_FILES.iter() {
+        let contents = fs::read_to_string(template_dir.join(file))?;
+        files.push((
+            crate_dir.join(file),
+            rename_template(contents.as_str(), day),
+        ));
+    }
+
+    let mut scaffold = Scaffold::default();
+    for (path, contents) in files.iter() {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, contents)?;
+        scaffold.created.push(path.clone());
+    }
+
+    update_file(&root.join("Cargo.toml"), &mut scaffold, |s| {
+        add_workspace_member(s, day)
+    })?;
+    update_file(&root.join("aoc").join("Cargo.toml"), &mut scaffold, |s| {
+        add_dependency(s, day)
+    })?;
+    update_file(
+        &root.join("aoc").join("src").join("days.rs"),
+        &mut scaffold,
+        |s| register_day(s, day),
+    )?;
+
+    let data = data_dir();
+    fs::create_dir_all(&data)?;
+    for name in [
+        format!("day{:02}-sample.txt", day),
+        format!("day{:02}.txt", day),
+    ] {
+        let path = data.join(name);
+        if !path.exists() {
+            fs::write(&path, "")?;
+            scaffold.created.push(path);
+        }
+    }
+    update_file(&data.join("answers.txt"), &mut scaffold, |s| {
+        add_answer_placeholders(s, day)
+    })?;
+
+    Ok(scaffold)
+}
+
+/// Rewrites the file with `f`, missing files start out empty
+fn update_file<F>(path: &Path, scaffold: &mut Scaffold, f: F) -> io::Result<()>
+where
+    F: FnOnce(&str) -> Option<String>,
+{
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => String::new(),
+        Err(err) => return Err(err),
+    };
+    if let Some(updated) = f(contents.as_str()) {
+        fs::write(path, updated)?;
+        scaffold.updated.push(path.to_path_buf());
+    }
+    Ok(())
+}
+
+/// Renames the crate, the solution, the bench target and the inputs of a template file
+fn rename_template(contents: &str, day: u8) -> String {
+    contents
+        .replace("day00", format!("day{:02}", day).as_str())
+        .replace("Day00", format!("Day{:02}", day).as_str())
+        .replace("\"00-sample\"", format!("\"{:02}-sample\"", day).as_str())
+        .replace("\"00\"", format!("\"{:02}\"", day).as_str())
+}
+
+/// Appends the crate to the workspace members, `None` if already a member
+fn add_workspace_member(manifest: &str, day: u8) -> Option<String> {
+    let member = format!("\"day{:02}\"", day);
+    let start = manifest.find("members = [")?;
+    let end = start + manifest[start..].find(']')?;
+    if manifest[start..end].contains(member.as_str()) {
+        return None;
+    }
+
+    // Keep the days together, right after the last one
+    let last_day = manifest[start..end]
+        .rmatch_indices("\"day")
+        .next()
+        .and_then(|(k, _)| {
+            let name_start = start + k + 1;
+            manifest[name_start..end]
+                .find('"')
+                .map(|q| name_start + q + 1)
+        });
+
+    let mut updated = manifest.to_string();
+    match last_day {
+        Some(k) => updated.insert_str(k, format!(", {}", member).as_str()),
+        None => updated.insert_str(
+            start + "members = [".len(),
+            format!(" {},", member).as_str(),
+        ),
+    }
+    Some(updated)
+}
+
+/// Adds the day crate as a dependency of the runner, `None` if already there
+fn add_dependency(manifest: &str, day: u8) -> Option<String> {
+    let name = format!("day{:02}", day);
+    let line = format!("{} = {{ path = \"../{}\" }}", name, name);
+    if manifest.lines().any(|l| l.starts_with(name.as_str())) {
+        return None;
+    }
+
+    let mut lines: Vec<&str> = manifest.lines().collect();
+    let insert_at = lines
+        .iter()
+        .rposition(|l| l.starts_with("day"))
+        .map(|k| k + 1)
+        .or_else(|| {
+            lines
+                .iter()
+                .position(|l| l.trim() == "[dependencies]")
+                .map(|k| k + 1)
+        })?;
+    lines.insert(insert_at, line.as_str());
+    Some(lines.join("\n") + "\n")
+}
+
+/// Appends the day to the runner registry, `None` if already registered
+fn register_day(source: &str, day: u8) -> Option<String> {
+    let solution = format!("day{:02}::Day{:02}", day, day);
+    if source.contains(solution.as_str()) {
+        return None;
+    }
+
+    let header_start = source.find("pub const DAYS: [Day; ")?;
+    let count_start = header_start + "pub const DAYS: [Day; ".len();
+    let count_end = count_start + source[count_start..].find(']')?;
+    let count: usize = source[count_start..count_end].parse().ok()?;
+    let list_end = count_end + source[count_end..].find("\n];")?;
+
+    let entry = format!(
+        "\n    Day {{\n        day: {},\n        parse: parse_puzzle::<{}>,\n    }},",
+        day, solution
+    );
+    Some(format!(
+        "{}{}{}{}{}",
+        &source[..count_start],
+        count + 1,
+        &source[count_end..list_end],
+        entry,
+        &source[list_end..]
+    ))
+}
+
+/// Adds commented answer lines to fill in once known, `None` if the day has answers
+fn add_answer_placeholders(answers: &str, day: u8) -> Option<String> {
+    let prefix = format!("{:02} ", day);
+    let commented = format!("# {:02} ", day);
+    if answers
+        .lines()
+        .any(|l| l.starts_with(prefix.as_str()) || l.starts_with(commented.as_str()))
+    {
+        return None;
+    }
+
+    let mut updated = answers.to_string();
+    if !updated.is_empty() && !updated.ends_with('\n') {
+        updated.push('\n');
+    }
+    for input in [format!("{:02}-sample", day), format!("{:02}", day)] {
+        for part in 1..=2 {
+            updated.push_str(format!("# {} {} <answer>\n", input, part).as_str());
+        }
+    }
+    Some(updated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_workspace_dir() {
+        let root = workspace_dir();
+        assert!(root
+            .components()
+            .all(|c| c != std::path::Component::ParentDir));
+        assert!(root.join("day00").join("Cargo.toml").exists());
+    }
+
+    #[test]
+    fn test_rename_template() {
+        let source = "use day00::{part1, part2};\nimpl Solution for Day00 {}\nget_puzzle_input(\"00-sample\");\nget_puzzle_input(\"00\");\n";
+        assert_eq!(
+            rename_template(source, 13),
+            "use day13::{part1, part2};\nimpl Solution for Day13 {}\nget_puzzle_input(\"13-sample\");\nget_puzzle_input(\"13\");\n".to_string()
+        );
+        assert_eq!(
+            rename_template("name = \"day00-bench\"", 7),
+            "name = \"day07-bench\"".to_string()
+        );
+    }
+
+    #[test]
+    fn test_add_workspace_member() {
+        let manifest = "members = [ \"day00\", \"day01\",\n    \"day12\", \"aoc\", \"input\",\n]\n";
+        assert_eq!(
+            add_workspace_member(manifest, 13),
+            Some("members = [ \"day00\", \"day01\",\n    \"day12\", \"day13\", \"aoc\", \"input\",\n]\n".to_string())
+        );
+        assert_eq!(add_workspace_member(manifest, 12), None);
+    }
+
+    #[test]
+    fn test_add_dependency() {
+        let manifest =
+            "[dependencies]\nday01 = { path = \"../day01\" }\ninput = { path = \"../input\" }\n";
+        assert_eq!(
+            add_dependency(manifest, 2),
+            Some("[dependencies]\nday01 = { path = \"../day01\" }\nday02 = { path = \"../day02\" }\ninput = { path = \"../input\" }\n".to_string())
+        );
+        assert_eq!(add_dependency(manifest, 1), None);
+    }
+
+    #[test]
+    fn test_register_day() {
+        let source = "pub const DAYS: [Day; 1] = [\n    Day {\n        day: 1,\n        parse: parse_puzzle::<day01::Day01>,\n    },\n];\n";
+        let expected = "pub const DAYS: [Day; 2] = [\n    Day {\n        day: 1,\n        parse: parse_puzzle::<day01::Day01>,\n    },\n    Day {\n        day: 2,\n        parse: parse_puzzle::<day02::Day02>,\n    },\n];\n";
+        assert_eq!(register_day(source, 2), Some(expected.to_string()));
+        assert_eq!(register_day(expected, 2), None);
+    }
+
+    #[test]
+    fn test_add_answer_placeholders() {
+        assert_eq!(
+            add_answer_placeholders("01 1 11", 13),
+            Some("01 1 11\n# 13-sample 1 <answer>\n# 13-sample 2 <answer>\n# 13 1 <answer>\n# 13 2 <answer>\n".to_string())
+        );
+        assert_eq!(add_answer_placeholders("# 13 1 <answer>\n", 13), None);
+    }
+}
@@ -0,0 +1,81 @@
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use input::read_puzzle_input;
+
+/// Which input file to feed into a day
+#[derive(Debug, Clone, PartialEq)]
+pub enum InputSelector {
+    /// The real puzzle input, ie: `data/day07.txt`
+    Day,
+    /// A named sample, ie: `sample2` reads `data/day12-sample2.txt`
+    Sample(String),
+    /// Any file on disk
+    Path(PathBuf),
+}
+
+impl InputSelector {
+    /// Name of the input as shown in reports
+    pub fn name(&self, day: u8) -> String {
+        match self {
+            InputSelector::Day => format!("{:02}", day),
+            InputSelector::Sample(sample) => format!("{:02}-{}", day, sample),
+            InputSelector::Path(path) => path.display().to_string(),
+        }
+    }
+
+    pub fn read(&self, day: u8) -> io::Result<String> {
+        match self {
+            InputSelector::Path(path) => fs::read_to_string(path),
+            _ => read_puzzle_input(self.name(day).as_str()),
+        }
+    }
+}
+
+impl FromStr for InputSelector {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if value.is_empty() {
+            return Err("Input must not be empty".to_string());
+        }
+        if value == "day" {
+            return Ok(InputSelector::Day);
+        }
+        // Anything that looks like a file is read as is
+        if value.contains('/') || value.contains('.') {
+            return Ok(InputSelector::Path(PathBuf::from(value)));
+        }
+        Ok(InputSelector::Sample(value.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_selector() {
+        assert_eq!("day".parse(), Ok(InputSelector::Day));
+        assert_eq!(
+            "sample2".parse(),
+            Ok(InputSelector::Sample("sample2".to_string()))
+        );
+        assert_eq!(
+            "./inputs/07.txt".parse(),
+            Ok(InputSelector::Path(PathBuf::from("./inputs/07.txt")))
+        );
+        assert!("".parse::<InputSelector>().is_err());
+    }
+
+    #[test]
+    fn test_selector_name() {
+        assert_eq!(InputSelector::Day.name(7), "07".to_string());
+        assert_eq!(
+            InputSelector::Sample("sample-loop1".to_string()).name(6),
+            "06-sample-loop1".to_string()
+        );
+    }
+}
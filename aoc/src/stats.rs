@@ -0,0 +1,111 @@
+use std::fmt;
+use std::time::Duration;
+
+use input::format_duration;
+
+/// Summary of the durations collected over repeated runs
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub p95: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    /// Computes the stats, returns `None` when there are no samples
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let runs = sorted.len();
+
+        let median = if runs.is_multiple_of(2) {
+            (sorted[runs / 2 - 1] + sorted[runs / 2]) / 2
+        } else {
+            sorted[runs / 2]
+        };
+
+        // Nearest rank percentile
+        let p95_rank = (runs * 95).div_ceil(100);
+        let p95 = sorted[p95_rank.max(1) - 1];
+
+        let nanos: Vec<f64> = sorted.iter().map(|d| d.as_nanos() as f64).collect();
+        let mean = nanos.iter().sum::<f64>() / runs as f64;
+        let variance = nanos.iter().map(|n| (n - mean).powi(2)).sum::<f64>() / runs as f64;
+
+        Some(Self {
+            runs,
+            min: sorted[0],
+            median,
+            mean: Duration::from_nanos(mean.round() as u64),
+            p95,
+            stddev: Duration::from_nanos(variance.sqrt().round() as u64),
+        })
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} runs, min: {}, median: {}, mean: {}, p95: {}, stddev: {}",
+            self.runs,
+            format_duration(self.min),
+            format_duration(self.median),
+            format_duration(self.mean),
+            format_duration(self.p95),
+            format_duration(self.stddev)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn micros(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|v| Duration::from_micros(*v)).collect()
+    }
+
+    #[test]
+    fn test_empty() {
+        assert_eq!(Stats::from_samples(&[]), None);
+    }
+
+    #[test]
+    fn test_single() {
+        let stats = Stats::from_samples(&micros(&[5])).unwrap();
+        assert_eq!(stats.min, Duration::from_micros(5));
+        assert_eq!(stats.median, Duration::from_micros(5));
+        assert_eq!(stats.p95, Duration::from_micros(5));
+        assert_eq!(stats.stddev, Duration::ZERO);
+    }
+
+    #[test]
+    fn test_stats() {
+        let stats = Stats::from_samples(&micros(&[9, 2, 4, 4, 5, 5, 7, 4])).unwrap();
+        assert_eq!(stats.runs, 8);
+        assert_eq!(stats.min, Duration::from_micros(2));
+        assert_eq!(
+            stats.median,
+            Duration::from_micros(4) + Duration::from_nanos(500)
+        );
+        assert_eq!(stats.mean, Duration::from_micros(5));
+        assert_eq!(stats.p95, Duration::from_micros(9));
+        assert_eq!(stats.stddev, Duration::from_micros(2));
+    }
+
+    #[test]
+    fn test_p95() {
+        let samples: Vec<u64> = (1..=100).collect();
+        let stats = Stats::from_samples(&micros(&samples)).unwrap();
+        assert_eq!(stats.p95, Duration::from_micros(95));
+        assert_eq!(stats.to_string().split(", ").nth(4), Some("p95: 95µs"));
+    }
+}
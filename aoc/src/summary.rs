@@ -0,0 +1,114 @@
+use std::time::Duration;
+
+use input::format_duration;
+
+use crate::alloc::format_bytes;
+use crate::report::Record;
+
+/// Formats the records as a table sorted by day and part.
+///
+/// The `highlight` slowest parts are marked, followed by a line with the
+/// total time spent on the parts and the wall-clock time of the whole run.
+pub fn summary_table(records: &[Record], wall_clock: Duration, highlight: usize) -> String {
+    let mut sorted: Vec<&Record> = records.iter().collect();
+    sorted.sort_by_key(|r| (r.day, r.part));
+
+    let mut by_duration: Vec<&Record> = sorted
+        .iter()
+        .copied()
+        .filter(|r| r.duration().is_some())
+        .collect();
+    by_duration.sort_by_key(|r| std::cmp::Reverse(r.duration()));
+    let slowest: Vec<(u8, u8)> = by_duration
+        .iter()
+        .take(highlight)
+        .map(|r| (r.day, r.part))
+        .collect();
+
+    let with_heap = sorted.iter().any(|r| r.allocs.is_some());
+    let mut header = format!("{:<4} {:<4} {:<20} {:>10}", "Day", "Part", "Answer", "Time");
+    if with_heap {
+        header.push_str(format!(" {:>12}", "Peak heap").as_str());
+    }
+    let mut lines: Vec<String> = vec![header];
+    for record in sorted.iter() {
+        let status = record.status.to_uppercase();
+        let answer = record.answer.as_deref().unwrap_or(status.as_str());
+        let time = match record.duration() {
+            Some(d) => format_duration(d),
+            None => "-".to_string(),
+        };
+        let mut line = format!(
+            "{:<4} {:<4} {:<20} {:>10}",
+            format!("{:02}", record.day),
+            record.part,
+            answer,
+            time
+        );
+        if with_heap {
+            let peak = match record.allocs {
+                Some(allocs) => format_bytes(allocs.peak),
+                None => "-".to_string(),
+            };
+            line.push_str(format!(" {:>12}", peak).as_str());
+        }
+        if let Some(rank) = slowest.iter().position(|k| *k == (record.day, record.part)) {
+            line.push_str(format!("  << slowest #{}", rank + 1).as_str());
+        }
+        lines.push(line);
+    }
+
+    let total: Duration = sorted.iter().filter_map(|r| r.duration()).sum();
+    let failed = sorted.iter().filter(|r| r.duration().is_none()).count();
+    lines.push(format!(
+        "{} parts, {} failed, total: {}, wall-clock: {}",
+        sorted.len(),
+        failed,
+        format_duration(total),
+        format_duration(wall_clock)
+    ));
+
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(day: u8, part: u8, micros: u64) -> Record {
+        Record {
+            day,
+            part,
+            input: format!("{:02}", day),
+            answer: Some(format!("{}", day * 10 + part)),
+            read: None,
+            parse: Some(Duration::from_micros(1)),
+            solve: Some(Duration::from_micros(micros)),
+            allocs: None,
+            status: "ok",
+        }
+    }
+
+    #[test]
+    fn test_summary_table() {
+        let records = vec![
+            record(2, 1, 9),
+            record(1, 2, 99),
+            Record::failed(3, 1, "03".to_string()),
+            record(1, 1, 49),
+        ];
+        let table = summary_table(&records, Duration::from_micros(120), 2);
+        let lines: Vec<&str> = table.lines().collect();
+
+        assert_eq!(lines.len(), 6);
+        assert!(lines[1].starts_with("01   1    11"));
+        assert!(lines[1].ends_with("50µs  << slowest #2"));
+        assert!(lines[2].ends_with("100µs  << slowest #1"));
+        assert!(lines[3].ends_with("10µs"));
+        assert!(lines[4].starts_with("03   1    ERROR"));
+        assert_eq!(
+            lines[5],
+            "4 parts, 1 failed, total: 160µs, wall-clock: 120µs"
+        );
+    }
+}
@@ -0,0 +1,81 @@
+use input::{Answer, Answers};
+
+use crate::days::DAYS;
+use crate::run_parts;
+use crate::selector::InputSelector;
+
+#[derive(Debug, PartialEq)]
+pub enum Status {
+    Pass,
+    Fail,
+    /// No answer recorded for this part
+    Missing,
+    /// The part could not run, ie: the input file is missing
+    Error(String),
+}
+
+#[derive(Debug)]
+pub struct Verification {
+    pub day: u8,
+    pub part: u8,
+    pub expected: Option<String>,
+    pub actual: Option<Answer>,
+    pub status: Status,
+}
+
+impl Verification {
+    pub fn passed(&self) -> bool {
+        self.status == Status::Pass
+    }
+}
+
+/// Runs every registered day and part on the real input
+/// and compares the results with the recorded answers
+pub fn verify_all(answers: &Answers) -> Vec<Verification> {
+    let selector = InputSelector::Day;
+    let mut result: Vec<Verification> = Vec::new();
+
+    for day in DAYS.iter() {
+        // Both parts share one parse, a read or parse error fails them both
+        let runs = match run_parts(day, &[1, 2], &selector, false) {
+            Ok(runs) => runs
+                .into_iter()
+                .map(|r| r.map_err(|e| e.to_string()))
+                .collect(),
+            Err(err) => vec![Err(err.to_string()), Err(err.to_string())],
+        };
+
+        for (part, run) in [1, 2].into_iter().zip(runs) {
+            let expected = answers
+                .get(selector.name(day.day).as_str(), part)
+                .map(|a| a.to_string());
+
+            let verification = match run {
+                Ok(run) => {
+                    let status = match expected.as_ref() {
+                        Some(e) if run.answer.matches(e) => Status::Pass,
+                        Some(_) => Status::Fail,
+                        None => Status::Missing,
+                    };
+                    Verification {
+                        day: day.day,
+                        part,
+                        expected,
+                        actual: Some(run.answer),
+                        status,
+                    }
+                }
+                Err(err) => Verification {
+                    day: day.day,
+                    part,
+                    expected,
+                    actual: None,
+                    status: Status::Error(err),
+                },
+            };
+            result.push(verification);
+        }
+    }
+
+    result
+}
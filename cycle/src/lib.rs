@@ -0,0 +1,165 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Describes where a sequence of states starts repeating itself
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    /// Index of the first state that is part of the cycle
+    pub start: usize,
+    /// Number of steps it takes to come back to the same state
+    pub length: usize,
+}
+
+/// Detects a cycle using Brent's algorithm.
+///
+/// `step` computes the next state from the current one and returns `None`
+/// when the sequence terminates, in which case there is no cycle.
+/// Only a couple of states are kept in memory at any time.
+pub fn brent<S, F>(initial: S, mut step: F) -> Option<Cycle>
+where
+    S: Clone + Eq,
+    F: FnMut(&S) -> Option<S>,
+{
+    // Find the cycle length by teleporting the tortoise to the hare
+    // every time the search window doubles
+    let mut power: usize = 1;
+    let mut length: usize = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial)?;
+
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare)?;
+        length += 1;
+    }
+
+    // Put the hare `length` steps ahead, then move both until they meet
+    // at the start of the cycle
+    let mut tortoise = initial.clone();
+    let mut hare = initial;
+    for _ in 0..length {
+        hare = step(&hare)?;
+    }
+
+    let mut start: usize = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise)?;
+        hare = step(&hare)?;
+        start += 1;
+    }
+
+    Some(Cycle { start, length })
+}
+
+/// Detects a cycle by remembering the index of every visited state.
+///
+/// Uses more memory than `brent` but never computes a state twice,
+/// which pays off when stepping is expensive.
+pub fn find_cycle<S, F>(initial: S, mut step: F) -> Option<Cycle>
+where
+    S: Clone + Eq + Hash,
+    F: FnMut(&S) -> Option<S>,
+{
+    let mut seen: HashMap<S, usize> = HashMap::new();
+    let mut current = initial;
+    let mut index: usize = 0;
+
+    loop {
+        if let Some(start) = seen.get(&current) {
+            return Some(Cycle {
+                start: *start,
+                length: index - start,
+            });
+        }
+
+        let next = step(&current)?;
+        seen.insert(current, index);
+        current = next;
+        index += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 0 -> 1 -> 2 -> 3 -> 4 -> 5 -> 6 -> 3 -> ...
+    fn with_tail(x: &u32) -> Option<u32> {
+        if *x < 3 {
+            Some(x + 1)
+        } else {
+            Some(3 + (x - 3 + 1) % 4)
+        }
+    }
+
+    fn terminating(x: &u32) -> Option<u32> {
+        if *x < 10 {
+            Some(x + 1)
+        } else {
+            None
+        }
+    }
+
+    #[test]
+    fn test_brent_with_tail() {
+        assert_eq!(
+            brent(0, with_tail),
+            Some(Cycle {
+                start: 3,
+                length: 4
+            })
+        );
+    }
+
+    #[test]
+    fn test_brent_pure_cycle() {
+        assert_eq!(
+            brent(0, |x: &u32| Some((x + 1) % 5)),
+            Some(Cycle {
+                start: 0,
+                length: 5
+            })
+        );
+        assert_eq!(
+            brent(7, |x: &u32| Some(*x)),
+            Some(Cycle {
+                start: 0,
+                length: 1
+            })
+        );
+    }
+
+    #[test]
+    fn test_brent_terminating() {
+        assert_eq!(brent(0, terminating), None);
+    }
+
+    #[test]
+    fn test_find_cycle_with_tail() {
+        assert_eq!(
+            find_cycle(0, with_tail),
+            Some(Cycle {
+                start: 3,
+                length: 4
+            })
+        );
+    }
+
+    #[test]
+    fn test_find_cycle_terminating() {
+        assert_eq!(find_cycle(0, terminating), None);
+    }
+
+    #[test]
+    fn test_algorithms_agree() {
+        for n in 1..50 {
+            // Pollard style sequence, always cycles eventually
+            let step = |x: &u64| Some((x * x + 1) % n);
+            assert_eq!(brent(2 % n, step), find_cycle(2 % n, step));
+        }
+    }
+}
@@ -1,18 +1,43 @@
-use day00::{part1, part2};
-use input::get_puzzle_input;
+use day00::Day00;
+use input::{puzzle_path, read_puzzle_input};
+
+const INPUT: &str = "00";
 
 fn main() {
-    divan::main();
+    let mut divan = divan::Divan::from_args();
+    // Puzzle inputs are not committed, skip the benches that need one
+    if let Err(err) = read_puzzle_input(INPUT) {
+        eprintln!(
+            "Skipping day00 puzzle benches, unable to read {}: {}",
+            puzzle_path(INPUT).display(),
+            err
+        );
+        divan = divan.skip_regex("(^|::)puzzle::");
+    }
+    divan.main();
 }
 
-#[divan::bench]
-fn part1_bench() {
-    let input_string = get_puzzle_input("00");
-    part1(divan::black_box(input_string.as_str()));
-}
+mod puzzle {
+    use divan::{black_box, Bencher};
+    use input::{get_puzzle_input, Solution};
+
+    use super::{Day00, INPUT};
+
+    #[divan::bench]
+    fn parse(bencher: Bencher) {
+        let input_string = get_puzzle_input(INPUT);
+        bencher.bench(|| Day00::parse(black_box(input_string.as_str())));
+    }
+
+    #[divan::bench]
+    fn part1(bencher: Bencher) {
+        let parsed = Day00::parse(get_puzzle_input(INPUT).as_str()).unwrap();
+        bencher.bench(|| Day00::part1(black_box(&parsed)));
+    }
 
-#[divan::bench]
-fn part2_bench() {
-    let input_string = get_puzzle_input("00");
-    part2(divan::black_box(input_string.as_str()));
+    #[divan::bench]
+    fn part2(bencher: Bencher) {
+        let parsed = Day00::parse(get_puzzle_input(INPUT).as_str()).unwrap();
+        bencher.bench(|| Day00::part2(black_box(&parsed)));
+    }
 }
@@ -1,9 +1,29 @@
-pub fn part1(input: &str) -> i32 {
-    solve_puzzle(input)
+use input::{Answer, Solution, SolveError};
+
+pub struct Day00;
+
+impl Solution for Day00 {
+    type Parsed = String;
+
+    fn parse(input: &str) -> Result<Self::Parsed, SolveError> {
+        Ok(input.to_string())
+    }
+
+    fn part1(parsed: &Self::Parsed) -> Result<Answer, SolveError> {
+        Ok(solve_puzzle(parsed).into())
+    }
+
+    fn part2(parsed: &Self::Parsed) -> Result<Answer, SolveError> {
+        Ok(solve_puzzle(parsed).into())
+    }
+}
+
+pub fn part1(input: &str) -> Result<Answer, SolveError> {
+    Day00::part1(&Day00::parse(input)?)
 }
 
-pub fn part2(input: &str) -> i32 {
-    solve_puzzle(input)
+pub fn part2(input: &str) -> Result<Answer, SolveError> {
+    Day00::part2(&Day00::parse(input)?)
 }
 
 fn solve_puzzle(_input: &str) -> i32 {
@@ -12,21 +32,11 @@ fn solve_puzzle(_input: &str) -> i32 {
 
 #[cfg(test)]
 mod tests {
-    use input::get_puzzle_input;
-
     use super::*;
 
-    #[test]
-    fn test_part1() {
-        let input = get_puzzle_input("00-sample");
-        let result = solve_puzzle(input.as_str());
-        assert_eq!(result, 0);
-    }
-
-    #[test]
-    fn test_part2() {
-        let input = get_puzzle_input("00-sample");
-        let result = solve_puzzle(input.as_str());
-        assert_eq!(result, 0);
+    input::sample_tests! {
+        Day00;
+        test_part1: "00-sample", part 1 => 0;
+        test_part2: "00-sample", part 2 => 0;
     }
 }
@@ -1,96 +1,111 @@
+use input::{lines, parse_all, Answer, ParseResult, Solution, SolveError};
 use itertools::Itertools;
 use nom::{
-    character::complete::{self, line_ending, space0},
-    multi::separated_list1,
+    character::complete::{self, space0},
     sequence::separated_pair,
-    IResult, Parser,
+    Parser,
 };
+use nom_supreme::ParserExt;
 
-pub fn part1(input: &str) -> i32 {
-    find_total_distances(input)
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Parsed = Vec<(u32, u32)>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, SolveError> {
+        parse_data(input)
+    }
+
+    fn part1(parsed: &Self::Parsed) -> Result<Answer, SolveError> {
+        Ok(find_total_distances(parsed)?.into())
+    }
+
+    fn part2(parsed: &Self::Parsed) -> Result<Answer, SolveError> {
+        Ok(find_similarity_score(parsed)?.into())
+    }
 }
 
-pub fn part2(input: &str) -> i32 {
-    find_similarity_score(input)
+pub fn part1(input: &str) -> Result<Answer, SolveError> {
+    Day01::part1(&Day01::parse(input)?)
 }
 
-fn find_total_distances(input: &str) -> i32 {
-    if let Ok((_, items)) = parse_table(input) {
-        let length = items.len();
-        let mut left: Vec<u32> = Vec::with_capacity(length);
-        let mut right: Vec<u32> = Vec::with_capacity(length);
+pub fn part2(input: &str) -> Result<Answer, SolveError> {
+    Day01::part2(&Day01::parse(input)?)
+}
 
-        for (l, r) in items.into_iter() {
-            left.push(l);
-            right.push(r);
-        }
+fn find_total_distances(items: &[(u32, u32)]) -> Result<u64, SolveError> {
+    let length = items.len();
+    let mut left: Vec<u32> = Vec::with_capacity(length);
+    let mut right: Vec<u32> = Vec::with_capacity(length);
 
-        left.sort();
-        right.sort();
+    for (l, r) in items.iter() {
+        left.push(*l);
+        right.push(*r);
+    }
 
-        let total: i32 = std::iter::zip(left, right)
-            .map(|(l, r)| (l as i32 - r as i32).abs())
-            .sum();
+    left.sort();
+    right.sort();
 
-        return total;
-    }
-    0
+    std::iter::zip(left, right)
+        .try_fold(0_u64, |total, (l, r)| {
+            total.checked_add(l.abs_diff(r) as u64)
+        })
+        .ok_or_else(|| SolveError::Overflow("total distance".to_string()))
 }
 
-fn find_similarity_score(input: &str) -> i32 {
-    if let Ok((_, items)) = parse_table(input) {
-        let length = items.len();
-        let mut left: Vec<u32> = Vec::with_capacity(length);
-        let mut right: Vec<u32> = Vec::with_capacity(length);
-
-        for (l, r) in items.into_iter() {
-            left.push(l);
-            right.push(r);
-        }
-
-        let counts = right.iter().counts();
-
-        let total: i32 = left
-            .iter()
-            .map(|x| {
-                if let Some(count) = counts.get(x) {
-                    return *count as i32 * *x as i32;
-                }
-                0
-            })
-            .sum();
-
-        return total;
+fn find_similarity_score(items: &[(u32, u32)]) -> Result<u64, SolveError> {
+    let length = items.len();
+    let mut left: Vec<u32> = Vec::with_capacity(length);
+    let mut right: Vec<u32> = Vec::with_capacity(length);
+
+    for (l, r) in items.iter() {
+        left.push(*l);
+        right.push(*r);
     }
 
-    0
+    let counts = right.iter().counts();
+
+    left.iter()
+        .try_fold(0_u64, |total, x| {
+            let count = counts.get(x).copied().unwrap_or(0) as u64;
+            total.checked_add(count.checked_mul(*x as u64)?)
+        })
+        .ok_or_else(|| SolveError::Overflow("similarity score".to_string()))
+}
+
+fn parse_data(input: &str) -> Result<Vec<(u32, u32)>, SolveError> {
+    parse_all(input, parse_table)
 }
 
-fn parse_table(lines: &str) -> IResult<&str, Vec<(u32, u32)>> {
-    separated_list1(line_ending, pair_parser).parse(lines)
+fn parse_table(data: &str) -> ParseResult<'_, Vec<(u32, u32)>> {
+    lines(pair_parser).parse(data)
 }
 
-fn pair_parser(line: &str) -> IResult<&str, (u32, u32)> {
-    separated_pair(complete::u32, space0, complete::u32).parse(line)
+fn pair_parser(line: &str) -> ParseResult<'_, (u32, u32)> {
+    separated_pair(complete::u32, space0, complete::u32)
+        .context("a pair of location ids")
+        .parse(line)
 }
 
 #[cfg(test)]
 mod tests {
-    use input::get_puzzle_input;
-
     use super::*;
 
+    input::sample_tests! {
+        Day01;
+        test_part1: "01-sample", part 1 => 11;
+        test_part2: "01-sample", part 2 => 31;
+    }
+
     #[test]
-    fn test_part1() {
-        let input = get_puzzle_input("01-sample");
-        let total = find_total_distances(input.as_str());
-        assert_eq!(total, 11);
+    fn test_large_ids() {
+        let items = [(u32::MAX, u32::MAX), (u32::MAX, 0), (0, u32::MAX)];
+        assert_eq!(find_total_distances(&items).unwrap(), 0);
+        assert_eq!(find_similarity_score(&items).unwrap(), 4 * u32::MAX as u64);
     }
 
     #[test]
-    fn test_part2() {
-        let input = get_puzzle_input("01-sample");
-        let total = find_similarity_score(input.as_str());
-        assert_eq!(total, 31);
+    fn test_generated() {
+        generator::check_solution::<Day01>(1, 200);
     }
 }
@@ -1,44 +1,52 @@
+use input::{end_of_line, lines, parse_all, Answer, ParseResult, Solution, SolveError};
 use nom::{
-    character::complete::{self, line_ending, space1},
-    multi::separated_list1,
-    IResult, Parser,
+    character::complete::{self, space1},
+    Parser,
 };
+use nom_supreme::{multi::collect_separated_terminated, ParserExt};
 
-pub fn part1(input: &str) -> i32 {
-    compute_safe_reports(input)
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Parsed = Vec<Vec<u32>>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, SolveError> {
+        parse_data(input)
+    }
+
+    fn part1(parsed: &Self::Parsed) -> Result<Answer, SolveError> {
+        Ok(compute_safe_reports(parsed).into())
+    }
+
+    fn part2(parsed: &Self::Parsed) -> Result<Answer, SolveError> {
+        Ok(compute_safe_reports_with_dampener(parsed).into())
+    }
 }
 
-pub fn part2(input: &str) -> i32 {
-    compute_safe_reports_with_dampener(input)
+pub fn part1(input: &str) -> Result<Answer, SolveError> {
+    Day02::part1(&Day02::parse(input)?)
 }
 
-fn compute_safe_reports(input: &str) -> i32 {
-    if let Ok((_, reports)) = parse_report(input) {
-        let total: i32 = reports.iter().map(|report| is_safe_count(report)).sum();
-        return total;
-    }
-    0
+pub fn part2(input: &str) -> Result<Answer, SolveError> {
+    Day02::part2(&Day02::parse(input)?)
 }
 
-fn compute_safe_reports_with_dampener(input: &str) -> i32 {
-    if let Ok((_, reports)) = parse_report(input) {
-        let total: i32 = reports
-            .iter()
-            .map(|report| is_safe_count_dampened(report))
-            .sum();
-        return total;
-    }
-    0
+fn compute_safe_reports(reports: &[Vec<u32>]) -> usize {
+    reports.iter().map(is_safe_count).sum()
 }
 
-fn is_safe_count(levels: &Vec<u32>) -> i32 {
+fn compute_safe_reports_with_dampener(reports: &[Vec<u32>]) -> usize {
+    reports.iter().map(is_safe_count_dampened).sum()
+}
+
+fn is_safe_count(levels: &Vec<u32>) -> usize {
     match is_safe(levels) {
         true => 1,
         false => 0,
     }
 }
 
-fn is_safe_count_dampened(levels: &Vec<u32>) -> i32 {
+fn is_safe_count_dampened(levels: &Vec<u32>) -> usize {
     if is_safe(levels) {
         return 1;
     }
@@ -51,7 +59,7 @@ fn is_safe_count_dampened(levels: &Vec<u32>) -> i32 {
 fn is_safe(levels: &Vec<u32>) -> bool {
     let mut asc: Option<bool> = None;
     for x in levels.windows(2) {
-        let diff: i32 = x[0] as i32 - x[1] as i32;
+        let diff: i64 = x[0] as i64 - x[1] as i64;
         if diff == 0 {
             // Not safe
             return false;
@@ -97,31 +105,84 @@ fn is_safe_dampened(levels: &Vec<u32>) -> bool {
     false
 }
 
-fn parse_report(input: &str) -> IResult<&str, Vec<Vec<u32>>> {
-    separated_list1(line_ending, row_parser).parse(input)
+fn parse_data(input: &str) -> Result<Vec<Vec<u32>>, SolveError> {
+    parse_all(input, parse_report)
+}
+
+fn parse_report(input: &str) -> ParseResult<'_, Vec<Vec<u32>>> {
+    lines(row_parser).parse(input)
 }
 
-fn row_parser(line: &str) -> IResult<&str, Vec<u32>> {
-    separated_list1(space1, complete::u32).parse(line)
+fn row_parser(line: &str) -> ParseResult<'_, Vec<u32>> {
+    collect_separated_terminated(complete::u32, space1, end_of_line)
+        .context("a report")
+        .parse(line)
 }
 
 #[cfg(test)]
 mod tests {
-    use input::get_puzzle_input;
+    use proptest::prelude::*;
 
     use super::*;
 
+    /// The puzzle rules as written: all steps go the same way, by 1 to 3 levels
+    fn reference_safe(levels: &[u32]) -> bool {
+        let steps: Vec<i64> = levels
+            .windows(2)
+            .map(|w| w[1] as i64 - w[0] as i64)
+            .collect();
+        steps.iter().all(|d| (1..=3).contains(d)) || steps.iter().all(|d| (-3..=-1).contains(d))
+    }
+
+    fn reference_safe_dampened(levels: &[u32]) -> bool {
+        reference_safe(levels)
+            || (0..levels.len()).any(|i| {
+                let mut dampened = levels.to_vec();
+                dampened.remove(i);
+                reference_safe(&dampened)
+            })
+    }
+
+    /// Mostly small steps so that safe reports are common
+    fn report() -> impl Strategy<Value = Vec<u32>> {
+        (0_u32..100, prop::collection::vec(-5_i32..=5, 0..8)).prop_map(|(start, steps)| {
+            let mut level = start as i32;
+            let mut levels = vec![start];
+            for step in steps {
+                level = (level + step).max(0);
+                levels.push(level as u32);
+            }
+            levels
+        })
+    }
+
+    input::sample_tests! {
+        Day02;
+        test_part1: "02-sample", part 1 => 2;
+        test_part2: "02-sample", part 2 => 4;
+    }
+
+    proptest! {
+        #[test]
+        fn prop_safe_matches_reference(levels in report()) {
+            prop_assert_eq!(is_safe(&levels), reference_safe(&levels));
+        }
+
+        #[test]
+        fn prop_dampener_matches_reference(levels in report()) {
+            let expected = reference_safe_dampened(&levels) as usize;
+            prop_assert_eq!(is_safe_count_dampened(&levels), expected);
+        }
+    }
+
     #[test]
-    fn test_part1() {
-        let input = get_puzzle_input("02-sample");
-        let total = compute_safe_reports(input.as_str());
-        assert_eq!(total, 2);
+    fn test_large_levels() {
+        assert!(!is_safe(&vec![u32::MAX, 0]));
+        assert!(is_safe(&vec![u32::MAX - 4, u32::MAX - 2, u32::MAX]));
     }
 
     #[test]
-    fn test_part2() {
-        let input = get_puzzle_input("02-sample");
-        let total = compute_safe_reports_with_dampener(input.as_str());
-        assert_eq!(total, 4);
+    fn test_generated() {
+        generator::check_solution::<Day02>(2, 200);
     }
 }
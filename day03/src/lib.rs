@@ -1,23 +1,33 @@
+use input::{Answer, Solution, SolveError};
+
 const MUL: &'static str = "mul(";
 const DO: &'static str = "do()";
 const DONT: &'static str = "don't()";
 
 #[derive(Debug)]
-struct Pair {
-    x: i32,
-    y: i32,
+pub struct Pair {
+    x: i64,
+    y: i64,
 }
 
 impl Pair {
-    fn new(x: i32, y: i32) -> Self {
+    fn new(x: i64, y: i64) -> Self {
         Self { x, y }
     }
 
-    fn mul(&self) -> i32 {
-        self.x * self.y
+    fn mul(&self) -> Option<i64> {
+        self.x.checked_mul(self.y)
     }
 }
 
+/// A single instruction found in the corrupted memory
+#[derive(Debug)]
+pub enum Instruction {
+    Mul(Pair),
+    Do,
+    Dont,
+}
+
 #[derive(Debug)]
 enum MarkerKind {
     Mul,
@@ -32,55 +42,86 @@ struct Marker {
     end: usize,
 }
 
-pub fn part1(input: &str) -> i32 {
-    solve_expressions(input, true)
-}
+pub struct Day03;
 
-pub fn part2(input: &str) -> i32 {
-    solve_expressions(input, false)
+impl Solution for Day03 {
+    type Parsed = Vec<Instruction>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, SolveError> {
+        // Corrupted memory is expected, anything that is not an instruction is skipped
+        Ok(parse_expressions(input))
+    }
+
+    fn part1(parsed: &Self::Parsed) -> Result<Answer, SolveError> {
+        Ok(solve_expressions(parsed, true)?.into())
+    }
+
+    fn part2(parsed: &Self::Parsed) -> Result<Answer, SolveError> {
+        Ok(solve_expressions(parsed, false)?.into())
+    }
 }
 
-fn solve_expressions(input: &str, always_on: bool) -> i32 {
-    parse_expressions(input, always_on)
+pub fn part1(input: &str) -> Result<Answer, SolveError> {
+    Day03::part1(&Day03::parse(input)?)
 }
 
-fn parse_expressions(input: &str, always_on: bool) -> i32 {
-    let mut total: i32 = 0;
+pub fn part2(input: &str) -> Result<Answer, SolveError> {
+    Day03::part2(&Day03::parse(input)?)
+}
 
-    let max_len = input.len();
-    let mut buffer = &input[..];
+fn solve_expressions(instructions: &[Instruction], always_on: bool) -> Result<i64, SolveError> {
+    let overflow = || SolveError::Overflow("sum of the multiplications".to_string());
+    let mut total: i64 = 0;
     let mut enabled = true;
 
-    while let Some(mark) = find_marker(buffer, always_on) {
-        let start = mark.end;
-        if start >= max_len {
-            break;
+    for instruction in instructions.iter() {
+        match instruction {
+            Instruction::Do => {
+                enabled = true;
+            }
+            Instruction::Dont => {
+                enabled = false;
+            }
+            Instruction::Mul(pair) => {
+                if enabled || always_on {
+                    let product = pair.mul().ok_or_else(overflow)?;
+                    total = total.checked_add(product).ok_or_else(overflow)?;
+                }
+            }
         }
+    }
 
+    Ok(total)
+}
+
+fn parse_expressions(input: &str) -> Vec<Instruction> {
+    let mut instructions: Vec<Instruction> = Vec::new();
+
+    let mut buffer = input;
+
+    while let Some(mark) = find_marker(buffer) {
         // Slice buffer to start on the end of the marker
-        buffer = &buffer[start..];
+        buffer = &buffer[mark.end..];
 
         match mark.kind {
             MarkerKind::Do => {
-                enabled = true;
+                instructions.push(Instruction::Do);
             }
             MarkerKind::Dont => {
-                enabled = false;
+                instructions.push(Instruction::Dont);
             }
             MarkerKind::Mul => {
-                if enabled {
-                    if let Some(pair) = find_expression(buffer) {
-                        total += pair.mul();
-                    }
+                if let Some(pair) = find_expression(buffer) {
+                    instructions.push(Instruction::Mul(pair));
                 }
             }
         }
     }
 
-    total
+    instructions
 }
 
-fn find_marker(buffer: &str, always_on: bool) -> Option<Marker> {
+fn find_marker(buffer: &str) -> Option<Marker> {
     let mul = buffer.find(MUL);
     let doit = buffer.find(DO);
     let dont = buffer.find(DONT);
@@ -93,10 +134,6 @@ fn find_marker(buffer: &str, always_on: bool) -> Option<Marker> {
             start: pos,
             end: pos + MUL.len(),
         });
-
-        if always_on {
-            return result;
-        }
     }
 
     if let Some(pos) = doit {
@@ -133,80 +170,88 @@ fn find_marker(buffer: &str, always_on: bool) -> Option<Marker> {
 }
 
 fn find_expression(buffer: &str) -> Option<Pair> {
-    // Find the first number until a ",", invalid char or eol
-    let mut first_num: Vec<char> = Vec::new();
-    let mut next_start: Option<usize> = None;
-
-    for (k, v) in buffer.chars().enumerate() {
-        if v == ',' {
-            // End of numbers?
-            if first_num.len() == 0 {
-                // Not good, bail out
-                return None;
-            }
-            if (k + 1) < buffer.len() {
-                next_start = Some(k + 1);
-            }
-            break;
-        } else if v.is_numeric() {
-            // Add more digits
-            first_num.push(v);
-        } else {
-            // Unexpected combination, bail out
-            return None;
-        }
-    }
-
-    let first_str: String = first_num.iter().cloned().collect();
-    let x: i32 = first_str.parse().unwrap();
-
-    // Find the next number until a ")", invalid char or eol
-    if let Some(next) = next_start {
-        let next_buffer = &buffer[next..];
-        let mut second_num: Vec<char> = Vec::new();
-        for v in next_buffer.chars() {
-            if v == ')' {
-                // End of second num?
-                if second_num.len() == 0 {
-                    // Not good, bail out
-                    return None;
-                }
-                // We're good to go, end the loop
-                break;
-            } else if v.is_numeric() {
-                second_num.push(v);
-            } else {
-                // Unexpected combination, bail out
-                return None;
-            }
-        }
-
-        let second_str: String = second_num.iter().clone().collect();
-        let y: i32 = second_str.parse().unwrap();
+    // The marker is already consumed, what follows must be `X,Y)`
+    let (x, rest) = take_number(buffer)?;
+    let rest = rest.strip_prefix(',')?;
+    let (y, rest) = take_number(rest)?;
+    rest.starts_with(')').then(|| Pair::new(x, y))
+}
 
-        return Some(Pair::new(x, y));
+/// Reads the 1 to 3 digit number the buffer starts with
+fn take_number(buffer: &str) -> Option<(i64, &str)> {
+    let len = buffer
+        .bytes()
+        .take(4)
+        .take_while(u8::is_ascii_digit)
+        .count();
+    if !(1..=3).contains(&len) {
+        return None;
     }
-
-    None
+    Some((buffer[..len].parse().ok()?, &buffer[len..]))
 }
 
 #[cfg(test)]
 mod tests {
-    use input::get_puzzle_input;
-
     use super::*;
 
+    input::sample_tests! {
+        Day03;
+        test_part1: "03-sample1", part 1 => 161;
+        test_part2: "03-sample2", part 2 => 48;
+    }
+
     #[test]
-    fn test_part1() {
-        let input = get_puzzle_input("03-sample1");
-        let total = solve_expressions(input.as_str(), true);
-        assert_eq!(total, 161);
+    fn test_find_expression() {
+        assert_eq!(find_expression("2,4)").and_then(|p| p.mul()), Some(8));
+        assert_eq!(
+            find_expression("123,999)xyz").and_then(|p| p.mul()),
+            Some(122877)
+        );
+        assert!(find_expression("1234,5)").is_none());
+        assert!(find_expression("2,4").is_none());
+        assert!(find_expression("2 ,4)").is_none());
+        assert!(find_expression(",4)").is_none());
+    }
+
+    #[test]
+    fn test_large_totals() {
+        // Past what an i32 holds
+        let instructions: Vec<Instruction> = (0..3000)
+            .map(|_| Instruction::Mul(Pair::new(999, 999)))
+            .collect();
+        assert_eq!(solve_expressions(&instructions, true), Ok(2_994_003_000));
+
+        let instructions = [
+            Instruction::Mul(Pair::new(i64::MAX, 1)),
+            Instruction::Mul(Pair::new(1, 1)),
+        ];
+        assert!(matches!(
+            solve_expressions(&instructions, true),
+            Err(SolveError::Overflow(_))
+        ));
+        assert!(Pair::new(i64::MAX, 2).mul().is_none());
+    }
+
+    #[test]
+    fn test_fuzz_regressions() {
+        // Inputs found by `aoc fuzz` that used to panic
+        for input in [
+            "",
+            "mul(",
+            "mul(4,\u{b2}",
+            "mul(mul(",
+            "mul(\u{e9}1,2)",
+            "mul(99999999999,1)",
+            "mul(2,99999999999)",
+        ] {
+            assert_eq!(part1(input), Ok(0.into()), "{:?}", input);
+            assert_eq!(part2(input), Ok(0.into()), "{:?}", input);
+        }
+        assert_eq!(part1("mul(2,3)do()"), Ok(6.into()));
     }
 
     #[test]
-    fn test_part2() {
-        let input = get_puzzle_input("03-sample2");
-        let total = solve_expressions(input.as_str(), false);
-        assert_eq!(total, 48);
+    fn test_generated() {
+        generator::check_solution::<Day03>(3, 50);
     }
 }
@@ -1,9 +1,11 @@
+use input::{Answer, Diagnostic, Solution, SolveError};
+
 const CX: u8 = b'X';
 const CM: u8 = b'M';
 const CA: u8 = b'A';
 const CS: u8 = b'S';
 
-const MOVERS: [[i32; 2]; 8] = [
+const MOVERS: [[i64; 2]; 8] = [
     [1, 0],
     [1, 1],
     [0, 1],
@@ -14,16 +16,33 @@ const MOVERS: [[i32; 2]; 8] = [
     [1, -1],
 ];
 
-pub fn part1(input: &str) -> i32 {
-    solve_puzzle(input)
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Parsed = Vec<Vec<u8>>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, SolveError> {
+        parse_matrix(input)
+    }
+
+    fn part1(parsed: &Self::Parsed) -> Result<Answer, SolveError> {
+        Ok(solve_puzzle(parsed).into())
+    }
+
+    fn part2(parsed: &Self::Parsed) -> Result<Answer, SolveError> {
+        Ok(solve_x_puzzle(parsed).into())
+    }
+}
+
+pub fn part1(input: &str) -> Result<Answer, SolveError> {
+    Day04::part1(&Day04::parse(input)?)
 }
 
-pub fn part2(input: &str) -> i32 {
-    solve_x_puzzle(input)
+pub fn part2(input: &str) -> Result<Answer, SolveError> {
+    Day04::part2(&Day04::parse(input)?)
 }
 
-fn solve_puzzle(input: &str) -> i32 {
-    let table = parse_matrix(input);
+fn solve_puzzle(table: &[Vec<u8>]) -> usize {
     let row_len = table.len();
     if row_len == 0 {
         return 0;
@@ -33,20 +52,18 @@ fn solve_puzzle(input: &str) -> i32 {
         return 0;
     }
 
-    let mut total: i32 = 0;
+    let mut total: usize = 0;
 
     for x in 0..row_len {
         for y in 0..col_len {
-            let patterns =
-                find_patterns(&table, x as i32, y as i32, row_len as i32, col_len as i32);
+            let patterns = find_patterns(table, x as i64, y as i64, row_len as i64, col_len as i64);
             total += patterns;
         }
     }
     total
 }
 
-fn solve_x_puzzle(input: &str) -> i32 {
-    let table = parse_matrix(input);
+fn solve_x_puzzle(table: &[Vec<u8>]) -> usize {
     let row_len = table.len();
     if row_len == 0 {
         return 0;
@@ -56,37 +73,45 @@ fn solve_x_puzzle(input: &str) -> i32 {
         return 0;
     }
 
-    let mut total: i32 = 0;
+    let mut total: usize = 0;
 
     for x in 0..row_len {
         for y in 0..col_len {
             let patterns =
-                find_x_patterns(&table, x as i32, y as i32, row_len as i32, col_len as i32);
+                find_x_patterns(table, x as i64, y as i64, row_len as i64, col_len as i64);
             total += patterns;
         }
     }
     total
 }
 
-fn parse_matrix(input: &str) -> Vec<Vec<u8>> {
+fn parse_matrix(input: &str) -> Result<Vec<Vec<u8>>, SolveError> {
     let mut row_len: Option<usize> = None;
     let mut rows: Vec<Vec<u8>> = Vec::new();
 
-    for line in input.lines() {
+    for (k, line) in input.lines().enumerate() {
         let row: Vec<u8> = line.as_bytes().to_vec();
         // Ensure it is a proper matrix
         if let Some(length) = row_len {
-            assert_eq!(length, row.len());
+            if length != row.len() {
+                let column = length.min(row.len());
+                let message = format!("expected {} letters, found {}", length, row.len());
+                return Err(Diagnostic::at_line(input, k, column, message).into());
+            }
         } else {
             row_len = Some(row.len());
         }
 
         rows.push(row);
     }
-    rows
+
+    if rows.is_empty() {
+        return Err(SolveError::Parse("empty word search".to_string()));
+    }
+    Ok(rows)
 }
 
-fn find_patterns(matrix: &Vec<Vec<u8>>, x: i32, y: i32, max_x: i32, max_y: i32) -> i32 {
+fn find_patterns(matrix: &[Vec<u8>], x: i64, y: i64, max_x: i64, max_y: i64) -> usize {
     // Find east
     // Find south east
     // Find south
@@ -95,7 +120,7 @@ fn find_patterns(matrix: &Vec<Vec<u8>>, x: i32, y: i32, max_x: i32, max_y: i32)
     // Find north west
     // Find north
     // Find north east
-    let mut result: i32 = 0;
+    let mut result: usize = 0;
 
     for pos in MOVERS.iter() {
         let x1 = pos[0];
@@ -136,7 +161,7 @@ fn find_patterns(matrix: &Vec<Vec<u8>>, x: i32, y: i32, max_x: i32, max_y: i32)
     result
 }
 
-fn find_x_patterns(matrix: &Vec<Vec<u8>>, x: i32, y: i32, max_x: i32, max_y: i32) -> i32 {
+fn find_x_patterns(matrix: &[Vec<u8>], x: i64, y: i64, max_x: i64, max_y: i64) -> usize {
     // Find pattern that looks like X of MAS, including reverse like SAM
     // A must be always at the center to make this work
     // M - S
@@ -198,21 +223,16 @@ fn find_x_patterns(matrix: &Vec<Vec<u8>>, x: i32, y: i32, max_x: i32, max_y: i32
 
 #[cfg(test)]
 mod tests {
-    use input::get_puzzle_input;
-
     use super::*;
 
-    #[test]
-    fn test_part1() {
-        let input = get_puzzle_input("04-sample");
-        let result = solve_puzzle(input.as_str());
-        assert_eq!(result, 18);
+    input::sample_tests! {
+        Day04;
+        test_part1: "04-sample", part 1 => 18;
+        test_part2: "04-sample", part 2 => 9;
     }
 
     #[test]
-    fn test_part2() {
-        let input = get_puzzle_input("04-sample");
-        let result = solve_x_puzzle(input.as_str());
-        assert_eq!(result, 9);
+    fn test_generated() {
+        generator::check_solution::<Day04>(4, 30);
     }
 }
@@ -1,18 +1,38 @@
 use std::collections::HashMap;
 
+use input::{end_of_line, lines, parse_all, Answer, ParseResult, Solution, SolveError};
 use nom::{
     character::complete::{self, char, line_ending},
-    multi::separated_list1,
-    sequence::separated_pair,
-    FindSubstring, IResult, Parser,
+    sequence::{pair, separated_pair},
+    Parser,
 };
+use nom_supreme::{multi::collect_separated_terminated, ParserExt};
+use tracing::{debug, instrument};
 
-pub fn part1(data: &str) -> i32 {
-    solve_puzzle(data)
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Parsed = PrintQueue;
+
+    fn parse(input: &str) -> Result<Self::Parsed, SolveError> {
+        parse_data(input)
+    }
+
+    fn part1(parsed: &Self::Parsed) -> Result<Answer, SolveError> {
+        Ok(solve_puzzle(parsed)?.into())
+    }
+
+    fn part2(parsed: &Self::Parsed) -> Result<Answer, SolveError> {
+        Ok(solve_puzzle2(parsed)?.into())
+    }
+}
+
+pub fn part1(data: &str) -> Result<Answer, SolveError> {
+    Day05::part1(&Day05::parse(data)?)
 }
 
-pub fn part2(data: &str) -> i32 {
-    solve_puzzle2(data)
+pub fn part2(data: &str) -> Result<Answer, SolveError> {
+    Day05::part2(&Day05::parse(data)?)
 }
 
 #[derive(Debug)]
@@ -21,40 +41,45 @@ struct Rule {
     right: i32,
 }
 
+/// Ordering rules and the page updates to check against them
 #[derive(Debug)]
-struct PageRuleMap {
+pub struct PrintQueue {
+    rules: PageRuleMap,
+    pages: Vec<Vec<i32>>,
+}
+
+impl PrintQueue {
+    pub fn rules(&self) -> &PageRuleMap {
+        &self.rules
+    }
+
+    pub fn updates(&self) -> &[Vec<i32>] {
+        &self.pages
+    }
+}
+
+/// The page ordering rules
+#[derive(Debug)]
+pub struct PageRuleMap {
+    /// Each page mapped to the pages that must come after it
     map: HashMap<i32, Vec<i32>>,
-    rules: Vec<Rule>,
 }
 
 impl PageRuleMap {
     fn new(rules: Vec<Rule>) -> Self {
-        PageRuleMap {
-            map: HashMap::new(),
-            rules,
+        let mut map: HashMap<i32, Vec<i32>> = HashMap::new();
+        for rule in rules.iter() {
+            map.entry(rule.left).or_default().push(rule.right);
         }
+        PageRuleMap { map }
     }
 
-    fn register_rules(&mut self, page: i32) {
-        if self.map.get(&page).is_some() {
-            return;
-        }
-
-        let rights: Vec<i32> = self
-            .rules
-            .iter()
-            .filter_map(|r| {
-                if r.left == page {
-                    return Some(r.right);
-                }
-                None
-            })
-            .collect();
-
-        self.map.insert(page, rights);
+    /// Pages that must come after the page when both are in an update
+    pub fn pages_after(&self, page: i32) -> &[i32] {
+        self.map.get(&page).map_or(&[], |pages| pages.as_slice())
     }
 
-    fn valid_pages(&mut self, pages: &Vec<i32>) -> bool {
+    fn valid_pages(&self, pages: &Vec<i32>) -> bool {
         for i in 0..pages.len() {
             if !self.valid_page(i, pages) {
                 return false;
@@ -63,18 +88,17 @@ impl PageRuleMap {
         true
     }
 
-    fn valid_page(&mut self, index: usize, pages: &Vec<i32>) -> bool {
+    fn valid_page(&self, index: usize, pages: &Vec<i32>) -> bool {
         self.valid_lefts(index, pages).is_ok()
     }
 
-    fn valid_lefts(&mut self, index: usize, pages: &Vec<i32>) -> Result<(), usize> {
-        if index <= 0 {
+    fn valid_lefts(&self, index: usize, pages: &Vec<i32>) -> Result<(), usize> {
+        if index == 0 {
             // Nothing else to check beyond this point
             return Ok(());
         }
 
         let val = pages[index];
-        self.register_rules(val);
 
         // All values to the left must not be listed in the right rules
         if let Some(rights) = self.map.get(&val) {
@@ -90,45 +114,47 @@ impl PageRuleMap {
     }
 }
 
-fn solve_puzzle(data: &str) -> i32 {
-    let (rules, pages) = parse_rules(data);
-    if rules.len() > 0 && pages.len() > 0 {
-        let mut worker = PageRuleMap::new(rules);
-        let mut result: i32 = 0;
+fn solve_puzzle(queue: &PrintQueue) -> Result<i64, SolveError> {
+    let worker = &queue.rules;
+    let pages = &queue.pages;
+    if !worker.map.is_empty() && !pages.is_empty() {
+        let mut result: i64 = 0;
 
         for i in 0..pages.len() {
             let cur_pages = &pages[i];
             if worker.valid_pages(cur_pages) {
-                result += find_middle_val(cur_pages);
+                result = add_middle_val(result, cur_pages)?;
             }
         }
-        return result;
+        return Ok(result);
     }
-    0
+    Ok(0)
 }
 
-fn solve_puzzle2(data: &str) -> i32 {
-    let (rules, pages) = parse_rules(data);
-    if rules.len() > 0 && pages.len() > 0 {
-        let mut worker = PageRuleMap::new(rules);
-        let mut result: i32 = 0;
+fn solve_puzzle2(queue: &PrintQueue) -> Result<i64, SolveError> {
+    let worker = &queue.rules;
+    let pages = &queue.pages;
+    if !worker.map.is_empty() && !pages.is_empty() {
+        let mut result: i64 = 0;
 
         for i in 0..pages.len() {
             let cur_pages = &pages[i];
             if !worker.valid_pages(cur_pages) {
                 // Fix invalid page
-                let fixed = fix_invalid_pages(&mut worker, cur_pages);
+                let fixed = fix_invalid_pages(worker, cur_pages)?;
                 // Get middle value and add result
-                result += find_middle_val(&fixed);
+                result = add_middle_val(result, &fixed)?;
             }
         }
 
-        return result;
+        return Ok(result);
     }
-    0
+    Ok(0)
 }
 
-fn fix_invalid_pages(worker: &mut PageRuleMap, pages: &Vec<i32>) -> Vec<i32> {
+/// Reorders the pages to follow the rules, rules that contradict each other can't be followed
+#[instrument(level = "debug", skip_all)]
+fn fix_invalid_pages(worker: &PageRuleMap, pages: &Vec<i32>) -> Result<Vec<i32>, SolveError> {
     let mut result = pages.clone();
 
     let mut left_valid = false;
@@ -169,61 +195,72 @@ fn fix_invalid_pages(worker: &mut PageRuleMap, pages: &Vec<i32>) -> Vec<i32> {
 
         breaker += 1;
         if breaker > 100 {
-            println!("left breaker...");
-            break;
+            debug!(?pages, "left breaker...");
+            return Err(SolveError::Unsolvable(format!(
+                "update {:?} can't be ordered, its rules contradict each other",
+                pages
+            )));
         }
     }
 
-    result
+    Ok(result)
 }
 
-fn parse_rules(data: &str) -> (Vec<Rule>, Vec<Vec<i32>>) {
-    // Split sections
-    let split_pos = data.find_substring("\n\n");
-    if let Some(pos) = split_pos {
-        let rules_str = &data[0..pos];
-        let pages_str = &data[(pos + 2)..];
+fn parse_data(data: &str) -> Result<PrintQueue, SolveError> {
+    let (rules, pages) = parse_all(data, rules_parser.and(pages_parser))?;
+    Ok(PrintQueue {
+        rules: PageRuleMap::new(rules),
+        pages,
+    })
+}
 
-        let rules = rules_parser(rules_str);
-        let pages = pages_parser(pages_str);
-        return (rules, pages);
-    }
-    (vec![], vec![])
+fn rules_parser(data: &str) -> ParseResult<'_, Vec<Rule>> {
+    // Rules and updates are separated by a blank line
+    collect_separated_terminated(rule_parser, line_ending, pair(line_ending, line_ending))
+        .map(|items: Vec<(i32, i32)>| {
+            items
+                .iter()
+                .map(|item| Rule {
+                    left: item.0,
+                    right: item.1,
+                })
+                .collect()
+        })
+        .parse(data)
 }
 
-fn rules_parser(data: &str) -> Vec<Rule> {
-    if let Ok((_, items)) = separated_list1(line_ending, rule_parser).parse(data) {
-        let result: Vec<Rule> = items
-            .iter()
-            .map(|item| Rule {
-                left: item.0,
-                right: item.1,
-            })
-            .collect();
-        return result;
-    }
-    vec![]
+fn rule_parser(data: &str) -> ParseResult<'_, (i32, i32)> {
+    separated_pair(complete::i32, char('|'), complete::i32)
+        .context("a rule")
+        .parse(data)
 }
 
-fn rule_parser(data: &str) -> IResult<&str, (i32, i32)> {
-    separated_pair(complete::i32, char('|'), complete::i32).parse(data)
+fn pages_parser(data: &str) -> ParseResult<'_, Vec<Vec<i32>>> {
+    lines(page_parser).parse(data)
 }
 
-fn pages_parser(data: &str) -> Vec<Vec<i32>> {
-    if let Ok((_, items)) = separated_list1(line_ending, page_parser).parse(data) {
-        return items;
-    }
-    vec![]
+fn page_parser(data: &str) -> ParseResult<'_, Vec<i32>> {
+    collect_separated_terminated(complete::i32, char(','), end_of_line)
+        .context("an update")
+        .parse(data)
 }
 
-fn page_parser(data: &str) -> IResult<&str, Vec<i32>> {
-    separated_list1(char(','), complete::i32).parse(data)
+fn add_middle_val(total: i64, pages: &Vec<i32>) -> Result<i64, SolveError> {
+    let middle = find_middle_val(pages)? as i64;
+    total
+        .checked_add(middle)
+        .ok_or_else(|| SolveError::Overflow("sum of the middle pages".to_string()))
 }
 
-fn find_middle_val(pages: &Vec<i32>) -> i32 {
-    assert!(pages.len() % 2 != 0, "Pages must be odd");
+fn find_middle_val(pages: &Vec<i32>) -> Result<i32, SolveError> {
+    if pages.len() % 2 == 0 {
+        return Err(SolveError::Validation(format!(
+            "update {:?} has an even number of pages, there is no middle page",
+            pages
+        )));
+    }
     let index: usize = pages.len() / 2;
-    pages[index]
+    Ok(pages[index])
 }
 
 #[cfg(test)]
@@ -236,20 +273,47 @@ mod tests {
     fn test_middle_val() {
         let arr: Vec<i32> = vec![1, 2, 3, 4, 5];
         let mid = find_middle_val(&arr);
-        assert_eq!(mid, 3);
+        assert_eq!(mid, Ok(3));
+        assert!(find_middle_val(&vec![1, 2]).is_err());
+
+        // Past what an i32 holds
+        assert_eq!(
+            add_middle_val(i32::MAX as i64, &arr),
+            Ok(i32::MAX as i64 + 3)
+        );
+        assert!(matches!(
+            add_middle_val(i64::MAX, &arr),
+            Err(SolveError::Overflow(_))
+        ));
+    }
+
+    input::sample_tests! {
+        Day05;
+        test_part1: "05-sample", part 1 => 143;
+        test_part2: "05-sample", part 2 => 123;
+    }
+
+    #[test]
+    fn test_contradicting_rules() {
+        let queue = parse_data("1|2\n2|1\n\n1,2,3\n").unwrap();
+        assert!(matches!(
+            solve_puzzle2(&queue),
+            Err(SolveError::Unsolvable(_))
+        ));
     }
 
     #[test]
-    fn test_part1() {
-        let input = get_puzzle_input("05-sample");
-        let result = solve_puzzle(input.as_str());
-        assert_eq!(result, 143);
+    fn test_parse_once() {
+        let queue = Day05::parse(&get_puzzle_input("05-sample")).unwrap();
+        assert_eq!(queue.updates().len(), 6);
+        assert_eq!(queue.rules().pages_after(97)[..2], [13, 61]);
+        assert!(queue.rules().pages_after(1).is_empty());
+        assert_eq!(Day05::part1(&queue), Ok(143.into()));
+        assert_eq!(Day05::part2(&queue), Ok(123.into()));
     }
 
     #[test]
-    fn test_part2() {
-        let input = get_puzzle_input("05-sample");
-        let result = solve_puzzle2(input.as_str());
-        assert_eq!(result, 123);
+    fn test_generated() {
+        generator::check_solution::<Day05>(5, 20);
     }
 }
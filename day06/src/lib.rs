@@ -1,6 +1,7 @@
 use std::collections::HashSet;
 
 use glam::IVec2;
+use input::{Answer, Diagnostic, Solution, SolveError};
 
 const CH_OBS: u8 = b'#';
 const CH_EMP: u8 = b'.';
@@ -10,7 +11,7 @@ const CH_GR: u8 = b'>';
 const CH_GD: u8 = b'v';
 const CH_GL: u8 = b'<';
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 enum Dir {
     Up,
     Right,
@@ -18,7 +19,7 @@ enum Dir {
     Left,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 struct Guard {
     pos: IVec2,
     dir: Dir,
@@ -66,8 +67,16 @@ enum CellItem {
     Guard,
 }
 
+/// Outcome of the guard trying to step forward
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Step {
+    Moved,
+    Blocked,
+}
+
+/// The lab map with the guard's starting position
 #[derive(Debug, Clone)]
-struct Grid {
+pub struct Grid {
     matrix: Vec<Vec<CellItem>>,
     max_x: i32,
     max_y: i32,
@@ -75,82 +84,83 @@ struct Grid {
 }
 
 impl Grid {
-    fn new(matrix: Vec<Vec<CellItem>>, guard: Guard) -> Self {
-        let max_x = (matrix.len() - 1) as i32;
-        assert!(max_x > 0, "There must be at least 1 row");
-        let max_y = (matrix[0].len() - 1) as i32;
+    pub fn rows(&self) -> usize {
+        self.matrix.len()
+    }
+
+    pub fn cols(&self) -> usize {
+        self.matrix[0].len()
+    }
+
+    /// Where the guard starts, as row and column
+    pub fn guard_start(&self) -> IVec2 {
+        self.guard.pos
+    }
 
-        Self {
+    fn new(matrix: Vec<Vec<CellItem>>, guard: Guard) -> Result<Self, SolveError> {
+        let rows = matrix.len();
+        let cols = matrix.first().map_or(0, |row| row.len());
+        if rows == 0 || cols == 0 {
+            return Err(SolveError::Validation(
+                "the lab map has no cells".to_string(),
+            ));
+        }
+
+        Ok(Self {
             matrix,
-            max_x,
-            max_y,
+            max_x: (rows - 1) as i32,
+            max_y: (cols - 1) as i32,
             guard,
-        }
+        })
     }
 
-    fn jump_next(&mut self) -> Option<CellItem> {
+    fn jump_next(&mut self) -> Option<Step> {
         let prev_pos = self.guard.pos;
         let pos = self.guard.forward();
 
-        match self.get_item(&pos) {
-            Some(val) => {
-                match *val {
-                    CellItem::Empty => {
-                        // Move the guard forward
-                        self.guard.set_pos(pos);
-                        // Set prev location empty
-                        self.fill_cell(&prev_pos, CellItem::Empty);
-
-                        Some(CellItem::Empty)
-                    }
-                    CellItem::Obs => {
-                        // Do not move forward, only return the obs
-                        Some(CellItem::Obs)
-                    }
-                    _ => panic!("Not expecting anything other than empty or obs"),
-                }
+        match self.get_item(&pos)? {
+            // The guard's starting cell is walkable too
+            CellItem::Empty | CellItem::Guard => {
+                // Move the guard forward
+                self.guard.set_pos(pos);
+                // Set prev location empty
+                self.fill_cell(&prev_pos, CellItem::Empty);
+
+                Some(Step::Moved)
             }
-            None => None,
+            // Do not move forward, only report the obs
+            CellItem::Obs => Some(Step::Blocked),
         }
     }
 
     /// Moves next and rotates if necessary
-    fn next(&mut self) -> Option<CellItem> {
-        match self.jump_next() {
-            Some(c) => match c {
-                CellItem::Empty => Some(CellItem::Empty),
-                CellItem::Obs => {
-                    self.guard.rotate();
-                    self.jump_next()
-                }
-                _ => panic!("Not expecting anything other than empty or obs"),
-            },
-            None => None,
+    fn next(&mut self) -> Option<Step> {
+        match self.jump_next()? {
+            Step::Moved => Some(Step::Moved),
+            Step::Blocked => {
+                self.guard.rotate();
+                self.jump_next()
+            }
         }
     }
 
-    /// Jumps to the next obstacle or the edge
-    fn quick_next(&mut self) -> Option<CellItem> {
-        let orig_pos = self.guard.pos;
-        let mut prev_pos: Option<IVec2> = None;
-        let mut pos = self.guard.forward();
+    /// Jumps to the next obstacle and rotates, without touching the grid.
+    /// Returns None when the guard walks off the edge.
+    fn quick_next(&self, guard: &Guard) -> Option<Guard> {
+        let mut next = guard.clone();
+        let mut pos = guard.forward();
 
         while let Some(item) = self.get_item(&pos) {
             match item {
-                CellItem::Empty => {
-                    // Try to move forward again?
-                    prev_pos = Some(pos.clone());
-                    pos = self.guard.lookup_next(&pos);
-                }
                 CellItem::Obs => {
-                    if let Some(prev) = prev_pos {
-                        self.guard.set_pos(prev);
-                        self.fill_cell(&orig_pos, CellItem::Empty);
-                    }
-                    self.guard.rotate();
-                    return Some(CellItem::Empty);
+                    next.rotate();
+                    return Some(next);
+                }
+                // The guard's starting cell is walkable too
+                CellItem::Empty | CellItem::Guard => {
+                    next.set_pos(pos);
+                    pos = next.forward();
                 }
-                _ => panic!("Not expecting anything other than empty or obs"),
             }
         }
 
@@ -187,16 +197,35 @@ impl Grid {
     }
 }
 
-pub fn part1(data: &str) -> i32 {
-    solve_puzzle(data)
+pub struct Day06;
+
+impl Solution for Day06 {
+    type Parsed = Grid;
+
+    fn parse(input: &str) -> Result<Self::Parsed, SolveError> {
+        parse_data(input)
+    }
+
+    fn part1(parsed: &Self::Parsed) -> Result<Answer, SolveError> {
+        Ok(solve_puzzle(parsed)?.into())
+    }
+
+    fn part2(parsed: &Self::Parsed) -> Result<Answer, SolveError> {
+        Ok(solve_puzzle_loops(parsed)?.into())
+    }
 }
 
-pub fn part2(data: &str) -> i32 {
-    solve_puzzle_loops(data)
+pub fn part1(data: &str) -> Result<Answer, SolveError> {
+    Day06::part1(&Day06::parse(data)?)
 }
 
-fn solve_puzzle(data: &str) -> i32 {
-    let mut grid = parse_data(data);
+pub fn part2(data: &str) -> Result<Answer, SolveError> {
+    Day06::part2(&Day06::parse(data)?)
+}
+
+fn solve_puzzle(orig_grid: &Grid) -> Result<usize, SolveError> {
+    ensure_guard_leaves(orig_grid)?;
+    let mut grid = orig_grid.clone();
 
     let mut moves: HashSet<IVec2> = HashSet::new();
     moves.insert(grid.guard.pos.clone());
@@ -205,13 +234,14 @@ fn solve_puzzle(data: &str) -> i32 {
         moves.insert(grid.guard.pos.clone());
     }
 
-    moves.len() as i32
+    Ok(moves.len())
 }
 
-fn solve_puzzle_loops(data: &str) -> i32 {
+fn solve_puzzle_loops(orig_grid: &Grid) -> Result<usize, SolveError> {
+    ensure_guard_leaves(orig_grid)?;
+
     // Run once to find all cells where we can insert an obstruction
     // Candicate cells are within the original path
-    let orig_grid = parse_data(data);
     let mut grid = orig_grid.clone();
     let start_pos = grid.guard.pos.clone();
     let mut blockers: HashSet<IVec2> = HashSet::new();
@@ -223,7 +253,7 @@ fn solve_puzzle_loops(data: &str) -> i32 {
     // Ensure to remove the starting pos
     blockers.remove(&start_pos);
 
-    let mut result = 0;
+    let mut result: usize = 0;
 
     for v in blockers.iter() {
         let mut test_grid = orig_grid.clone();
@@ -235,88 +265,154 @@ fn solve_puzzle_loops(data: &str) -> i32 {
         }
     }
 
-    result
+    Ok(result)
 }
 
-fn has_loop(mut grid: Grid) -> bool {
-    let mut fast_grid = grid.clone();
-    let mut looping = false;
-
-    loop {
-        let g1 = grid.quick_next();
-        fast_grid.quick_next();
-        let g2 = fast_grid.quick_next();
-
-        match (g1, g2) {
-            (None, None) => {
-                break;
-            }
-            (None, Some(_)) => {
-                break;
-            }
-            (Some(_), None) => {
-                break;
-            }
-            (Some(_), Some(_)) => {
-                // Compare the positions of the guards
-                if grid.guard.pos.eq(&fast_grid.guard.pos) && grid.guard.dir == fast_grid.guard.dir
-                {
-                    looping = true;
-                    break;
-                }
-            }
-        };
+/// Walking the guard's path never ends when it is already stuck in a loop
+fn ensure_guard_leaves(grid: &Grid) -> Result<(), SolveError> {
+    if has_loop(grid.clone()) {
+        return Err(SolveError::Unsolvable(
+            "the guard walks in a loop and never leaves the lab".to_string(),
+        ));
     }
+    Ok(())
+}
 
-    looping
+fn has_loop(grid: Grid) -> bool {
+    // The guard loops when it comes back to the same position and direction
+    cycle::brent(grid.guard.clone(), |guard| grid.quick_next(guard)).is_some()
 }
 
-fn parse_data(data: &str) -> Grid {
+fn parse_data(data: &str) -> Result<Grid, SolveError> {
     let mut matrix: Vec<Vec<CellItem>> = Vec::new();
     let mut guard: Option<Guard> = None;
 
     for (x, v) in data.lines().enumerate() {
         let mut row: Vec<CellItem> = Vec::new();
         for (y, c) in v.as_bytes().iter().enumerate() {
-            let cv = match *c {
-                CH_OBS => CellItem::Obs,
-                CH_GU => {
-                    assert!(guard.is_none(), "There must be no guard yet");
-                    guard = Some(Guard::new(IVec2::new(x as i32, y as i32), Dir::Up));
-                    CellItem::Guard
-                }
-                CH_GR => {
-                    assert!(guard.is_none(), "There must be no guard yet");
-                    guard = Some(Guard::new(IVec2::new(x as i32, y as i32), Dir::Right));
-                    CellItem::Guard
+            let dir = match *c {
+                CH_OBS => {
+                    row.push(CellItem::Obs);
+                    continue;
                 }
-                CH_GD => {
-                    assert!(guard.is_none(), "There must be no guard yet");
-                    guard = Some(Guard::new(IVec2::new(x as i32, y as i32), Dir::Down));
-                    CellItem::Guard
+                CH_EMP => {
+                    row.push(CellItem::Empty);
+                    continue;
                 }
-                CH_GL => {
-                    assert!(guard.is_none(), "There must be no guard yet");
-                    guard = Some(Guard::new(IVec2::new(x as i32, y as i32), Dir::Left));
-                    CellItem::Guard
+                CH_GU => Dir::Up,
+                CH_GR => Dir::Right,
+                CH_GD => Dir::Down,
+                CH_GL => Dir::Left,
+                _ => {
+                    let diagnostic = Diagnostic::at_line(data, x, y, "");
+                    return Err(diagnostic.expected("one of `.#^>v<`").into());
                 }
-                CH_EMP => CellItem::Empty,
-                _ => panic!("Invalid cell value."),
             };
-            row.push(cv);
+
+            if guard.is_some() {
+                return Err(SolveError::Validation(format!(
+                    "line {}, column {}: there can only be one guard",
+                    x + 1,
+                    y + 1
+                )));
+            }
+            guard = Some(Guard::new(IVec2::new(x as i32, y as i32), dir));
+            row.push(CellItem::Guard);
+        }
+
+        if let Some(first) = matrix.first() {
+            if first.len() != row.len() {
+                let column = first.len().min(row.len());
+                let message = format!("expected {} cells, found {}", first.len(), row.len());
+                return Err(Diagnostic::at_line(data, x, column, message).into());
+            }
         }
         matrix.push(row);
     }
 
-    Grid::new(matrix, guard.expect("Guard must be present"))
+    let Some(guard) = guard else {
+        return Err(SolveError::Validation(
+            "the lab map has no guard".to_string(),
+        ));
+    };
+    Grid::new(matrix, guard)
 }
 
 #[cfg(test)]
 mod tests {
     use input::get_puzzle_input;
+    use proptest::prelude::*;
 
     use super::*;
 
+    /// A random lab map, the guard's cell is never an obstacle
+    fn lab() -> impl Strategy<Value = String> {
+        (1_usize..10, 1_usize..10)
+            .prop_flat_map(|(rows, cols)| {
+                (
+                    prop::collection::vec(prop::bool::weighted(0.2), rows * cols),
+                    (0..rows, 0..cols),
+                    prop::sample::select(vec!['^', '>', 'v', '<']),
+                    Just(cols),
+                )
+            })
+            .prop_map(|(obstacles, start, guard, cols)| {
+                let cell = |x: usize, y: usize| match obstacles[x * cols + y] {
+                    _ if (x, y) == start => guard,
+                    true => '#',
+                    false => '.',
+                };
+                (0..obstacles.len() / cols)
+                    .map(|x| (0..cols).map(|y| cell(x, y)).collect::<String>())
+                    .collect::<Vec<String>>()
+                    .join("\n")
+            })
+    }
+
+    /// Cells visited one step at a time with `Grid::next`, `None` if the guard loops
+    fn stepped_path(grid: &Grid) -> Option<HashSet<IVec2>> {
+        let mut grid = grid.clone();
+        let mut seen: HashSet<Guard> = HashSet::new();
+        let mut cells: HashSet<IVec2> = HashSet::from([grid.guard.pos]);
+
+        while grid.next().is_some() {
+            if !seen.insert(grid.guard.clone()) {
+                return None;
+            }
+            cells.insert(grid.guard.pos);
+        }
+        Some(cells)
+    }
+
+    /// Cells covered by the jumps of `quick_next`, `None` if the guard loops
+    fn jumped_path(grid: &Grid) -> Option<HashSet<IVec2>> {
+        let mut seen: HashSet<Guard> = HashSet::new();
+        let mut cells: HashSet<IVec2> = HashSet::new();
+        let mut guard = grid.guard.clone();
+
+        loop {
+            if !seen.insert(guard.clone()) {
+                return None;
+            }
+            let next = grid.quick_next(&guard);
+
+            // Fill in the straight line skipped by the jump, up to the edge when leaving
+            let mut pos = guard.pos;
+            cells.insert(pos);
+            while grid.is_inside(&guard.lookup_next(&pos))
+                && next.as_ref().is_none_or(|n| n.pos != pos)
+            {
+                pos = guard.lookup_next(&pos);
+                cells.insert(pos);
+            }
+
+            match next {
+                Some(next) => guard = next,
+                None => return Some(cells),
+            }
+        }
+    }
+
     #[test]
     fn test_rotate() {
         let pos = IVec2::new(5, 5);
@@ -342,42 +438,79 @@ mod tests {
     #[test]
     fn test_loop1() {
         let data = get_puzzle_input("06-sample-loop1");
-        let grid = parse_data(data.as_str());
+        let grid = parse_data(data.as_str()).unwrap();
         assert!(has_loop(grid));
     }
 
     #[test]
     fn test_loop2() {
         let data = get_puzzle_input("06-sample-loop2");
-        let grid = parse_data(data.as_str());
+        let grid = parse_data(data.as_str()).unwrap();
         assert!(has_loop(grid));
     }
 
     #[test]
     fn test_loop3() {
         let data = get_puzzle_input("06-sample-loop3");
-        let grid = parse_data(data.as_str());
+        let grid = parse_data(data.as_str()).unwrap();
         assert!(has_loop(grid));
     }
 
     #[test]
     fn test_no_loop() {
         let data = get_puzzle_input("06-sample");
-        let grid = parse_data(data.as_str());
+        let grid = parse_data(data.as_str()).unwrap();
         assert!(!has_loop(grid));
     }
 
     #[test]
-    fn test_part1() {
-        let input = get_puzzle_input("06-sample");
-        let result = solve_puzzle(input.as_str());
-        assert_eq!(result, 41);
+    fn test_invalid_map() {
+        assert!(matches!(
+            parse_data("..#\n.x^\n"),
+            Err(SolveError::Parse(_))
+        ));
+        assert!(matches!(
+            parse_data("..#\n...\n"),
+            Err(SolveError::Validation(_))
+        ));
+        let stuck = parse_data(".#.\n#^#\n.#.\n").unwrap();
+        assert!(matches!(
+            solve_puzzle(&stuck),
+            Err(SolveError::Unsolvable(_))
+        ));
+    }
+
+    input::sample_tests! {
+        Day06;
+        test_part1: "06-sample", part 1 => 41;
+        test_part2: "06-sample", part 2 => 6;
+    }
+
+    proptest! {
+        #[test]
+        fn prop_quick_next_matches_next(lab in lab()) {
+            let grid = parse_data(lab.as_str()).unwrap();
+            let stepped = stepped_path(&grid);
+
+            prop_assert_eq!(jumped_path(&grid), stepped.clone());
+            prop_assert_eq!(has_loop(grid.clone()), stepped.is_none());
+            if let Some(cells) = stepped {
+                prop_assert_eq!(solve_puzzle(&grid).unwrap(), cells.len());
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_once() {
+        let grid = Day06::parse(&get_puzzle_input("06-sample")).unwrap();
+        assert_eq!((grid.rows(), grid.cols()), (10, 10));
+        assert_eq!(grid.guard_start(), IVec2::new(6, 4));
+        assert_eq!(Day06::part1(&grid), Ok(41.into()));
+        assert_eq!(Day06::part2(&grid), Ok(6.into()));
     }
 
     #[test]
-    fn test_part2() {
-        let input = get_puzzle_input("06-sample");
-        let result = solve_puzzle_loops(input.as_str());
-        assert_eq!(result, 6);
+    fn test_generated() {
+        generator::check_solution::<Day06>(6, 20);
     }
 }
@@ -1,77 +1,109 @@
+use input::{end_of_line, lines, parse_all, Answer, ParseResult, Solution, SolveError};
 use nom::{
-    character::complete::{self, char, line_ending, space1},
-    multi::separated_list1,
+    character::complete::{self, char, space1},
     sequence::separated_pair,
-    IResult, Parser,
+    Parser,
 };
+use nom_supreme::{multi::collect_separated_terminated, ParserExt};
 
-const PLUS: char = '+';
-const TIMES: char = '*';
-const CAT: char = '|';
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Op {
+    Plus,
+    Times,
+    /// Concatenates the digits of both sides
+    Cat,
+}
 
-const OPS: [char; 2] = ['+', '*'];
-const OPS2: [char; 3] = ['+', '*', '|'];
+const OPS: [Op; 2] = [Op::Plus, Op::Times];
+const OPS2: [Op; 3] = [Op::Plus, Op::Times, Op::Cat];
 
 #[derive(Debug)]
-struct Equation {
+pub struct Equation {
     result: i64,
     numbers: Vec<i64>,
 }
 
-pub fn part1(input: &str) -> i64 {
-    solve_puzzle(input)
+impl Equation {
+    pub fn result(&self) -> i64 {
+        self.result
+    }
+
+    pub fn numbers(&self) -> &[i64] {
+        &self.numbers
+    }
 }
 
-pub fn part2(input: &str) -> i64 {
-    solve_puzzle2(input)
+pub struct Day07;
+
+impl Solution for Day07 {
+    type Parsed = Vec<Equation>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, SolveError> {
+        parse_data(input)
+    }
+
+    fn part1(parsed: &Self::Parsed) -> Result<Answer, SolveError> {
+        Ok(solve_puzzle(parsed)?.into())
+    }
+
+    fn part2(parsed: &Self::Parsed) -> Result<Answer, SolveError> {
+        Ok(solve_puzzle2(parsed)?.into())
+    }
 }
 
-fn solve_puzzle(data: &str) -> i64 {
-    let items = parse_data(data);
-    items
-        .iter()
-        .map(|item| compute_callibration(item, &OPS))
-        .sum()
+pub fn part1(input: &str) -> Result<Answer, SolveError> {
+    Day07::part1(&Day07::parse(input)?)
+}
+
+pub fn part2(input: &str) -> Result<Answer, SolveError> {
+    Day07::part2(&Day07::parse(input)?)
+}
+
+fn solve_puzzle(items: &[Equation]) -> Result<i64, SolveError> {
+    sum_callibrations(items, &OPS)
+}
+
+fn solve_puzzle2(items: &[Equation]) -> Result<i64, SolveError> {
+    sum_callibrations(items, &OPS2)
 }
 
-fn solve_puzzle2(data: &str) -> i64 {
-    let items = parse_data(data);
+fn sum_callibrations(items: &[Equation], ops: &[Op]) -> Result<i64, SolveError> {
     items
         .iter()
-        .map(|item| compute_callibration(item, &OPS2))
-        .sum()
+        .try_fold(0_i64, |total, item| {
+            total.checked_add(compute_callibration(item, ops))
+        })
+        .ok_or_else(|| SolveError::Overflow("total calibration result".to_string()))
 }
 
-fn compute_callibration(eq: &Equation, ops: &[char]) -> i64 {
+fn compute_callibration(eq: &Equation, ops: &[Op]) -> i64 {
     let mut result: i64 = 0;
-    try_combinations(eq, &mut result, eq.numbers.len() - 1, ops);
+    try_combinations(eq, &mut result, ops);
     result
 }
 
-fn try_combinations(eq: &Equation, result: &mut i64, size: usize, ops: &[char]) {
-    let mut current: Vec<char> = vec![PLUS; size];
-    generate_ops_inner(eq, result, size, ops, &mut current);
+fn try_combinations(eq: &Equation, result: &mut i64, ops: &[Op]) {
+    generate_ops_inner(eq, result, 1, eq.numbers[0], ops);
 }
 
-fn generate_ops_inner(
-    eq: &Equation,
-    result: &mut i64,
-    size: usize,
-    ops: &[char],
-    current: &mut Vec<char>,
-) {
-    if size == 0 {
+/// Picks the operator before each remaining number, left to right, carrying the value so far
+fn generate_ops_inner(eq: &Equation, result: &mut i64, next: usize, value: i64, ops: &[Op]) {
+    if value > eq.result {
+        // Every number is positive so the value can only grow from here
+        return;
+    }
+    if next == eq.numbers.len() {
         // A full list of operators just got completed
-        // Try if this set solves the equation
-        if solve_eq(&eq.numbers, current) == eq.result {
+        if value == eq.result {
             *result = eq.result;
         }
         return;
     }
 
-    for ch in ops.iter() {
-        current[size - 1] = *ch;
-        generate_ops_inner(eq, result, size - 1, ops, current);
+    for op in ops.iter() {
+        if let Some(value) = apply_op(value, *op, eq.numbers[next]) {
+            generate_ops_inner(eq, result, next + 1, value, ops);
+        }
 
         if *result > 0 {
             // Already found an answer
@@ -80,52 +112,68 @@ fn generate_ops_inner(
     }
 }
 
-fn solve_eq(numbers: &Vec<i64>, operators: &Vec<char>) -> i64 {
-    assert!(numbers.len() > 0, "Numbers size should be > 0");
+/// `None` when the value overflows, it can only grow from there so it can't be the answer
+fn apply_op(left: i64, op: Op, right: i64) -> Option<i64> {
+    match op {
+        Op::Plus => left.checked_add(right),
+        Op::Times => left.checked_mul(right),
+        Op::Cat => left
+            .checked_mul(10_i64.checked_pow(right.ilog10() + 1)?)?
+            .checked_add(right),
+    }
+}
+
+#[cfg(test)]
+fn solve_eq(numbers: &[i64], operators: &[Op]) -> Option<i64> {
     assert_eq!(
         numbers.len(),
         operators.len() + 1,
         "Operators size must be less than 1 compared to the numbers size"
     );
-    let mut answer = numbers[0];
-    for (k, op) in operators.iter().enumerate() {
-        let right = numbers[k + 1];
-        if op == &PLUS {
-            answer += right;
-        } else if op == &TIMES {
-            answer *= right;
-        } else if op == &CAT {
-            let str_num = format!("{}{}", answer, right);
-            answer = str_num.parse().unwrap();
-        }
-    }
-    answer
+    operators
+        .iter()
+        .zip(numbers[1..].iter())
+        .try_fold(numbers[0], |answer, (op, right)| {
+            apply_op(answer, *op, *right)
+        })
 }
 
-fn parse_data(data: &str) -> Vec<Equation> {
-    if let Ok((_, items)) = separated_list1(line_ending, eq_line_parser).parse(data) {
-        let result: Vec<Equation> = items
-            .into_iter()
-            .map(|(ans, nums)| Equation {
-                result: ans,
-                numbers: nums,
-            })
-            .collect();
-        return result;
+fn parse_data(data: &str) -> Result<Vec<Equation>, SolveError> {
+    let items = parse_all(data, lines(eq_line_parser))?;
+
+    let mut result: Vec<Equation> = Vec::with_capacity(items.len());
+    for (k, (ans, nums)) in items.into_iter().enumerate() {
+        // Concatenation and the early exit on a found answer only work on positive numbers
+        if ans <= 0 || nums.iter().any(|n| *n <= 0) {
+            return Err(SolveError::Validation(format!(
+                "line {}: numbers must be positive",
+                k + 1
+            )));
+        }
+        result.push(Equation {
+            result: ans,
+            numbers: nums,
+        });
     }
-    vec![]
+    Ok(result)
 }
 
-fn eq_line_parser(data: &str) -> IResult<&str, (i64, Vec<i64>)> {
-    separated_pair(complete::i64, char(':'), numbers_parser).parse(data)
+fn eq_line_parser(data: &str) -> ParseResult<'_, (i64, Vec<i64>)> {
+    separated_pair(complete::i64, char(':').and(space1), numbers_parser)
+        .context("an equation")
+        .parse(data)
 }
 
-fn numbers_parser(data: &str) -> IResult<&str, Vec<i64>> {
-    separated_list1(space1, complete::i64).parse(data.trim())
+fn numbers_parser(data: &str) -> ParseResult<'_, Vec<i64>> {
+    collect_separated_terminated(complete::i64, space1, end_of_line).parse(data)
 }
 
 #[cfg(test)]
 mod tests {
+    use std::sync::mpsc;
+    use std::thread;
+    use std::time::Duration;
+
     use input::get_puzzle_input;
 
     use super::*;
@@ -133,28 +181,76 @@ mod tests {
     #[test]
     fn test_solve_eq() {
         let numbers: Vec<i64> = vec![10, 20, 30, 40];
-        let ops: Vec<char> = vec![PLUS, PLUS, PLUS];
-        assert_eq!(100, solve_eq(&numbers, &ops));
+        let ops: Vec<Op> = vec![Op::Plus, Op::Plus, Op::Plus];
+        assert_eq!(Some(100), solve_eq(&numbers, &ops));
     }
 
     #[test]
     fn test_solve_eq_mixed() {
         let numbers: Vec<i64> = vec![10, 20, 30, 40];
-        let ops: Vec<char> = vec![TIMES, PLUS, PLUS];
-        assert_eq!(270, solve_eq(&numbers, &ops));
+        let ops: Vec<Op> = vec![Op::Times, Op::Plus, Op::Plus];
+        assert_eq!(Some(270), solve_eq(&numbers, &ops));
+    }
+
+    #[test]
+    fn test_solve_eq_overflow() {
+        let numbers: Vec<i64> = vec![749744447674974444, 76];
+        assert_eq!(None, solve_eq(&numbers, &[Op::Times]));
+        assert_eq!(None, solve_eq(&numbers, &[Op::Cat]));
+    }
+
+    #[test]
+    fn test_total_overflow() {
+        let input =
+            "9000000000000000000: 9000000000000000000\n9000000000000000000: 9000000000000000000\n";
+        assert!(matches!(part1(input), Err(SolveError::Overflow(_))));
+        assert!(matches!(part2(input), Err(SolveError::Overflow(_))));
+        assert_eq!(
+            part1("9000000000000000000: 9000000000000000000"),
+            Ok(9000000000000000000_i64.into())
+        );
+    }
+
+    #[test]
+    fn test_fuzz_regressions() {
+        // Inputs found by `aoc fuzz` that used to panic
+        for input in ["6: 749744447674974444 76", "8: 43393974339397 1133 18 7"] {
+            assert_eq!(part1(input), Ok(0_i64.into()), "{:?}", input);
+            assert_eq!(part2(input), Ok(0_i64.into()), "{:?}", input);
+        }
+    }
+
+    #[test]
+    fn test_fuzz_timeout() {
+        // Found by `aoc fuzz`, too many operators to try them all
+        let input = "7161091938: 43 3 97 11 33 18 7 11 33 1 33 187 11 33 1 33 18 7 11 33 18 77";
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || sender.send((part1(input), part2(input))));
+        let (answer1, answer2) = receiver
+            .recv_timeout(Duration::from_secs(5))
+            .expect("took longer than 5 seconds");
+        assert_eq!(answer1, Ok(0_i64.into()));
+        assert_eq!(answer2, Ok(0_i64.into()));
+    }
+
+    input::sample_tests! {
+        Day07;
+        test_part1: "07-sample", part 1 => 3749;
+        test_part2: "07-sample", part 2 => 11387;
     }
 
     #[test]
-    fn test_part1() {
-        let input = get_puzzle_input("07-sample");
-        let result = solve_puzzle(input.as_str());
-        assert_eq!(result, 3749);
+    fn test_parse_once() {
+        let equations = Day07::parse(&get_puzzle_input("07-sample")).unwrap();
+        assert_eq!(equations.len(), 9);
+        assert_eq!(equations[0].result(), 190);
+        assert_eq!(equations[0].numbers(), [10, 19]);
+        assert_eq!(Day07::part1(&equations), Ok(3749.into()));
+        assert_eq!(Day07::part2(&equations), Ok(11387.into()));
     }
 
     #[test]
-    fn test_part2() {
-        let input = get_puzzle_input("07-sample");
-        let result = solve_puzzle2(input.as_str());
-        assert_eq!(result, 11387);
+    fn test_generated() {
+        generator::check_solution::<Day07>(7, 30);
     }
 }
@@ -1,18 +1,72 @@
-use day08::{part1, part2};
-use input::get_puzzle_input;
+use day08::Day08;
+use input::{puzzle_path, read_puzzle_input};
+
+const INPUT: &str = "08";
 
 fn main() {
-    divan::main();
+    let mut divan = divan::Divan::from_args();
+    // Puzzle inputs are not committed, skip the benches that need one
+    if let Err(err) = read_puzzle_input(INPUT) {
+        eprintln!(
+            "Skipping day08 puzzle benches, unable to read {}: {}",
+            puzzle_path(INPUT).display(),
+            err
+        );
+        divan = divan.skip_regex("(^|::)puzzle::");
+    }
+    divan.main();
 }
 
-#[divan::bench]
-fn part1_bench() {
-    let input_string = get_puzzle_input("08");
-    part1(divan::black_box(input_string.as_str()));
+mod puzzle {
+    use divan::{black_box, Bencher};
+    use input::{get_puzzle_input, Solution};
+
+    use super::{Day08, INPUT};
+
+    #[divan::bench]
+    fn parse(bencher: Bencher) {
+        let input_string = get_puzzle_input(INPUT);
+        bencher.bench(|| Day08::parse(black_box(input_string.as_str())));
+    }
+
+    #[divan::bench]
+    fn part1(bencher: Bencher) {
+        let parsed = Day08::parse(get_puzzle_input(INPUT).as_str()).unwrap();
+        bencher.bench(|| Day08::part1(black_box(&parsed)));
+    }
+
+    #[divan::bench]
+    fn part2(bencher: Bencher) {
+        let parsed = Day08::parse(get_puzzle_input(INPUT).as_str()).unwrap();
+        bencher.bench(|| Day08::part2(black_box(&parsed)));
+    }
 }
 
-#[divan::bench]
-fn part2_bench() {
-    let input_string = get_puzzle_input("08");
-    part2(divan::black_box(input_string.as_str()));
+/// Synthetic inputs of growing size, see `generator::day08` for what size means
+mod generated {
+    use divan::{black_box, Bencher};
+    use generator::{day08, BENCH_SEED};
+    use input::Solution;
+
+    use super::Day08;
+
+    const SIZES: [usize; 3] = [10, 25, 50];
+
+    #[divan::bench(args = SIZES)]
+    fn parse(bencher: Bencher, size: usize) {
+        let input_string = day08::generate(BENCH_SEED, size);
+        bencher.bench(|| Day08::parse(black_box(input_string.as_str())));
+    }
+
+    #[divan::bench(args = SIZES)]
+    fn part1(bencher: Bencher, size: usize) {
+        let parsed = Day08::parse(day08::generate(BENCH_SEED, size).as_str()).unwrap();
+        bencher.bench(|| Day08::part1(black_box(&parsed)));
+    }
+
+    #[divan::bench(args = SIZES)]
+    fn part2(bencher: Bencher, size: usize) {
+        let parsed = Day08::parse(day08::generate(BENCH_SEED, size).as_str()).unwrap();
+        bencher.bench(|| Day08::part2(black_box(&parsed)));
+    }
 }
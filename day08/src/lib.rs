@@ -2,17 +2,37 @@ use std::collections::{HashMap, HashSet};
 
 use glam::IVec2;
 use grid::coord_greater;
+use input::{Answer, Diagnostic, Solution, SolveError};
 
-pub fn part1(input: &str) -> i32 {
-    solve_puzzle(input)
+pub struct Day08;
+
+impl Solution for Day08 {
+    type Parsed = Coverage;
+
+    fn parse(input: &str) -> Result<Self::Parsed, SolveError> {
+        parse_data(input)
+    }
+
+    fn part1(parsed: &Self::Parsed) -> Result<Answer, SolveError> {
+        Ok(solve_puzzle(parsed).into())
+    }
+
+    fn part2(parsed: &Self::Parsed) -> Result<Answer, SolveError> {
+        Ok(solve_puzzle_harmonics(parsed).into())
+    }
 }
 
-pub fn part2(input: &str) -> i32 {
-    solve_puzzle_harmonics(input)
+pub fn part1(input: &str) -> Result<Answer, SolveError> {
+    Day08::part1(&Day08::parse(input)?)
 }
 
-#[derive(Debug)]
-struct Coverage {
+pub fn part2(input: &str) -> Result<Answer, SolveError> {
+    Day08::part2(&Day08::parse(input)?)
+}
+
+/// The antenna map along with the antinodes plotted so far
+#[derive(Debug, Clone)]
+pub struct Coverage {
     matrix: Vec<Vec<char>>,
     max_x: i32,
     max_y: i32,
@@ -219,31 +239,53 @@ impl Coverage {
     }
 }
 
-fn solve_puzzle(input: &str) -> i32 {
-    let mut coverage = parse_data(input);
+fn solve_puzzle(orig_coverage: &Coverage) -> usize {
+    let mut coverage = orig_coverage.clone();
     let towers = coverage.find_towers();
 
     for tower in towers.iter() {
         let pairs = coverage.scan_section(tower);
         coverage.plot_antinodes(tower, &pairs);
     }
-    coverage.antinodes.len() as i32
+    coverage.antinodes.len()
 }
 
-fn solve_puzzle_harmonics(input: &str) -> i32 {
-    let mut coverage = parse_data(input);
+fn solve_puzzle_harmonics(orig_coverage: &Coverage) -> usize {
+    let mut coverage = orig_coverage.clone();
     let towers = coverage.find_towers();
 
     for tower in towers.iter() {
         let pairs = coverage.scan_section(tower);
         coverage.plot_harmonics_antinodes(tower, &pairs);
     }
-    coverage.antinodes.len() as i32
+    coverage.antinodes.len()
 }
 
-fn parse_data(data: &str) -> Coverage {
+fn parse_data(data: &str) -> Result<Coverage, SolveError> {
     let matrix: Vec<Vec<char>> = data.lines().map(|line| line.chars().collect()).collect();
-    Coverage::new(matrix)
+
+    for (x, row) in matrix.iter().enumerate() {
+        if row.len() != matrix[0].len() {
+            let column = matrix[0].len().min(row.len());
+            let message = format!("expected {} cells, found {}", matrix[0].len(), row.len());
+            return Err(Diagnostic::at_line(data, x, column, message).into());
+        }
+        // Antinodes from the puzzle examples are fine, they are not antennas
+        if let Some(y) = row
+            .iter()
+            .position(|c| !c.is_alphanumeric() && *c != '.' && *c != '#')
+        {
+            let diagnostic = Diagnostic::at_line(data, x, y, "");
+            return Err(diagnostic.expected("`.` or an antenna").into());
+        }
+    }
+
+    if matrix.len() < 2 || matrix[0].len() < 2 {
+        return Err(SolveError::Validation(
+            "the map must be at least 2 by 2".to_string(),
+        ));
+    }
+    Ok(Coverage::new(matrix))
 }
 
 fn get_next_coord(a: &IVec2, b: &IVec2) -> IVec2 {
@@ -262,8 +304,6 @@ fn get_prev_coord(a: &IVec2, b: &IVec2) -> IVec2 {
 
 #[cfg(test)]
 mod tests {
-    use input::get_puzzle_input;
-
     use super::*;
 
     #[test]
@@ -298,17 +338,14 @@ mod tests {
         );
     }
 
-    #[test]
-    fn test_part1() {
-        let input = get_puzzle_input("08-sample");
-        let result = solve_puzzle(input.as_str());
-        assert_eq!(result, 14);
+    input::sample_tests! {
+        Day08;
+        test_part1: "08-sample", part 1 => 14;
+        test_part2: "08-sample", part 2 => 34;
     }
 
     #[test]
-    fn test_part2() {
-        let input = get_puzzle_input("08-sample");
-        let result = solve_puzzle_harmonics(input.as_str());
-        assert_eq!(result, 34);
+    fn test_generated() {
+        generator::check_solution::<Day08>(8, 30);
     }
 }
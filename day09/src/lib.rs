@@ -1,41 +1,79 @@
-pub fn part1(input: &str) -> i64 {
-    solve_puzzle(input)
+use input::{Answer, Diagnostic, Solution, SolveError};
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    type Parsed = DiskMap;
+
+    fn parse(input: &str) -> Result<Self::Parsed, SolveError> {
+        Ok(DiskMap {
+            blocks: parse_data(input)?,
+        })
+    }
+
+    fn part1(parsed: &Self::Parsed) -> Result<Answer, SolveError> {
+        Ok(solve_puzzle(&parsed.blocks)?.into())
+    }
+
+    fn part2(parsed: &Self::Parsed) -> Result<Answer, SolveError> {
+        Ok(solve_puzzle_contiguous(&parsed.blocks)?.into())
+    }
 }
 
-pub fn part2(input: &str) -> i64 {
-    solve_puzzle_contiguous(input)
+pub fn part1(input: &str) -> Result<Answer, SolveError> {
+    Day09::part1(&Day09::parse(input)?)
 }
 
-fn solve_puzzle(data: &str) -> i64 {
-    let blocks = parse_data(data);
-    let mut entries = format_blocks(&blocks);
-    defrag_entries(&mut entries);
+pub fn part2(input: &str) -> Result<Answer, SolveError> {
+    Day09::part2(&Day09::parse(input)?)
+}
 
-    let total: i64 = entries
-        .iter()
-        .enumerate()
-        .map(|(k, b)| match b {
-            DiskEntry::File(f) => f.id as i64 * (k as i64),
-            DiskEntry::Space => 0,
-        })
-        .sum();
-    total
+/// The dense disk map as alternating file and free space blocks
+#[derive(Debug, Clone)]
+pub struct DiskMap {
+    blocks: Vec<Block>,
+}
+
+impl DiskMap {
+    /// Number of files on the disk
+    pub fn files(&self) -> usize {
+        self.blocks.len().div_ceil(2)
+    }
+
+    /// Size of the disk in blocks, free space included
+    pub fn size(&self) -> usize {
+        self.blocks
+            .iter()
+            .map(|b| match b {
+                Block::File(f) => f.blocks as usize,
+                Block::Space(s) => s.blocks as usize,
+            })
+            .sum()
+    }
+}
+
+fn solve_puzzle(blocks: &[Block]) -> Result<u64, SolveError> {
+    let mut entries = format_blocks(blocks);
+    defrag_entries(&mut entries);
+    checksum(&entries)
 }
 
-fn solve_puzzle_contiguous(data: &str) -> i64 {
-    let blocks = parse_data(data);
-    let mut entries = format_blocks(&blocks);
+fn solve_puzzle_contiguous(blocks: &[Block]) -> Result<u64, SolveError> {
+    let mut entries = format_blocks(blocks);
     defrag_entries_contiguous(&mut entries);
+    checksum(&entries)
+}
 
-    let total: i64 = entries
+/// Sum of every block position multiplied by the id of the file it holds
+fn checksum(entries: &[DiskEntry]) -> Result<u64, SolveError> {
+    entries
         .iter()
         .enumerate()
-        .map(|(k, b)| match b {
-            DiskEntry::File(f) => f.id as i64 * (k as i64),
-            DiskEntry::Space => 0,
+        .try_fold(0_u64, |total, (k, b)| match b {
+            DiskEntry::File(f) => total.checked_add((f.id as u64).checked_mul(k as u64)?),
+            DiskEntry::Space => Some(total),
         })
-        .sum();
-    total
+        .ok_or_else(|| SolveError::Overflow("filesystem checksum".to_string()))
 }
 
 #[derive(Debug, Clone)]
@@ -46,7 +84,7 @@ enum Block {
 
 #[derive(Debug, Clone)]
 struct FileBlock {
-    id: i32,
+    id: usize,
     blocks: u8,
 }
 
@@ -63,35 +101,41 @@ enum DiskEntry {
 
 #[derive(Debug, Clone, Copy)]
 struct FileEntry {
-    id: i32,
+    id: usize,
 }
 
 #[derive(Debug, Clone, Copy)]
 struct FileEntryIndex {
     index: usize,
     length: usize,
-    id: i32,
+    id: usize,
 }
 
-fn parse_data(data: &str) -> Vec<Block> {
-    let blocks: Vec<Block> = data
-        .trim()
-        .chars()
-        .enumerate()
-        .map(|(k, v)| {
-            let n: u8 = v.to_string().parse().unwrap();
-            if k % 2 == 0 {
-                let id: i32 = k as i32 / 2;
-                Block::File(FileBlock { id, blocks: n })
-            } else {
-                Block::Space(SpaceBlock { blocks: n })
-            }
-        })
-        .collect();
-    blocks
+fn parse_data(input: &str) -> Result<Vec<Block>, SolveError> {
+    let data = input.trim();
+    let start = input.len() - input.trim_start().len();
+    if data.is_empty() {
+        return Err(SolveError::Parse("empty disk map".to_string()));
+    }
+
+    let mut blocks: Vec<Block> = Vec::with_capacity(data.len());
+    for (k, (offset, v)) in data.char_indices().enumerate() {
+        let Some(n) = v.to_digit(10) else {
+            let diagnostic = Diagnostic::at(input, start + offset, "");
+            return Err(diagnostic.expected("a digit").into());
+        };
+        let n = n as u8;
+        if k % 2 == 0 {
+            let id = k / 2;
+            blocks.push(Block::File(FileBlock { id, blocks: n }));
+        } else {
+            blocks.push(Block::Space(SpaceBlock { blocks: n }));
+        }
+    }
+    Ok(blocks)
 }
 
-fn format_blocks(blocks: &Vec<Block>) -> Vec<DiskEntry> {
+fn format_blocks(blocks: &[Block]) -> Vec<DiskEntry> {
     let mut entries: Vec<DiskEntry> = Vec::new();
     for block in blocks.iter() {
         match block {
@@ -114,6 +158,9 @@ fn defrag_entries(entries: &mut Vec<DiskEntry>) {
     // Move the rightmost file block to the left most space block
     // One at a time
     // Until no more gaps left to be filled
+    if entries.is_empty() {
+        return;
+    }
     let mut l: usize = 0;
     let mut r: usize = entries.len() - 1;
 
@@ -343,7 +390,7 @@ mod tests {
     #[test]
     fn test_blocks_str() {
         let data = get_puzzle_input("09-sample");
-        let blocks = parse_data(data.as_str());
+        let blocks = parse_data(data.as_str()).unwrap();
         let blocks_str = format_blocks_str(&blocks);
         assert_eq!(blocks_str, "2333133121414131402".to_string(),);
 
@@ -366,7 +413,7 @@ mod tests {
     #[test]
     fn test_blocks_str_contiguous() {
         let data = get_puzzle_input("09-sample");
-        let blocks = parse_data(data.as_str());
+        let blocks = parse_data(data.as_str()).unwrap();
         let blocks_str = format_blocks_str(&blocks);
         assert_eq!(blocks_str, "2333133121414131402".to_string(),);
 
@@ -387,17 +434,46 @@ mod tests {
         );
     }
 
+    input::sample_tests! {
+        Day09;
+        test_part1: "09-sample", part 1 => 1928;
+        test_part2: "09-sample", part 2 => 2858;
+    }
+
+    #[test]
+    fn test_checksum() {
+        let file = |id| DiskEntry::File(FileEntry { id });
+        assert_eq!(checksum(&[file(7), DiskEntry::Space, file(3)]), Ok(6));
+        assert_eq!(
+            checksum(&[file(0), file(usize::MAX)]),
+            Ok(usize::MAX as u64)
+        );
+        assert!(matches!(
+            checksum(&[file(0), file(usize::MAX), file(usize::MAX)]),
+            Err(SolveError::Overflow(_))
+        ));
+    }
+
+    #[test]
+    fn test_fuzz_regressions() {
+        // Inputs found by `aoc fuzz` that used to panic
+        for input in ["0", "00", "0\n"] {
+            assert_eq!(part1(input), Ok(0_u64.into()), "{:?}", input);
+            assert_eq!(part2(input), Ok(0_u64.into()), "{:?}", input);
+        }
+    }
+
     #[test]
-    fn test_part1() {
-        let input = get_puzzle_input("09-sample");
-        let result = solve_puzzle(input.as_str());
-        assert_eq!(result, 1928);
+    fn test_parse_once() {
+        let disk = Day09::parse(&get_puzzle_input("09-sample")).unwrap();
+        assert_eq!(disk.files(), 10);
+        assert_eq!(disk.size(), 42);
+        assert_eq!(Day09::part1(&disk), Ok(1928.into()));
+        assert_eq!(Day09::part2(&disk), Ok(2858.into()));
     }
 
     #[test]
-    fn test_part2() {
-        let input = get_puzzle_input("09-sample");
-        let result = solve_puzzle_contiguous(input.as_str());
-        assert_eq!(result, 2858);
+    fn test_generated() {
+        generator::check_solution::<Day09>(9, 200);
     }
 }
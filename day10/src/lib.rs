@@ -1,37 +1,54 @@
 use std::collections::{HashSet, VecDeque};
 
 use glam::IVec2;
+use input::{Answer, Diagnostic, Solution, SolveError};
 
-pub fn part1(input: &str) -> i32 {
-    solve_puzzle(input)
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Parsed = Grid;
+
+    fn parse(input: &str) -> Result<Self::Parsed, SolveError> {
+        parse_data(input)
+    }
+
+    fn part1(parsed: &Self::Parsed) -> Result<Answer, SolveError> {
+        Ok(solve_puzzle(parsed).into())
+    }
+
+    fn part2(parsed: &Self::Parsed) -> Result<Answer, SolveError> {
+        Ok(solve_puzzle_trails(parsed).into())
+    }
+}
+
+pub fn part1(input: &str) -> Result<Answer, SolveError> {
+    Day10::part1(&Day10::parse(input)?)
 }
 
-pub fn part2(input: &str) -> i32 {
-    solve_puzzle_trails(input)
+pub fn part2(input: &str) -> Result<Answer, SolveError> {
+    Day10::part2(&Day10::parse(input)?)
 }
 
-fn solve_puzzle(input: &str) -> i32 {
-    let grid = parse_data(input);
+fn solve_puzzle(grid: &Grid) -> usize {
     let mut trail_heads: HashSet<(IVec2, IVec2)> = HashSet::new();
 
     for start in grid.starts.iter() {
-        find_trail_heads(&grid, start, &mut trail_heads);
+        find_trail_heads(grid, start, &mut trail_heads);
     }
 
     // For each starting position, find all trails that leads to an end of trail
-    trail_heads.len() as i32
+    trail_heads.len()
 }
 
-fn solve_puzzle_trails(input: &str) -> i32 {
-    let grid = parse_data(input);
+fn solve_puzzle_trails(grid: &Grid) -> usize {
     let mut trails: HashSet<Vec<IVec2>> = HashSet::new();
 
     for start in grid.starts.iter() {
-        find_distinct_trails(&grid, start, &mut trails);
+        find_distinct_trails(grid, start, &mut trails);
     }
 
     // For each starting position, find all trails that leads to an end of trail
-    trails.len() as i32
+    trails.len()
 }
 
 fn find_trail_heads(grid: &Grid, start: &IVec2, result: &mut HashSet<(IVec2, IVec2)>) {
@@ -125,8 +142,9 @@ fn find_trails_inner(
     }
 }
 
+/// The topographic map with all the trailheads
 #[derive(Debug)]
-struct Grid {
+pub struct Grid {
     rows: Vec<Vec<u8>>,
     max_x: i32,
     max_y: i32,
@@ -167,41 +185,49 @@ impl Grid {
     }
 }
 
-fn parse_data(data: &str) -> Grid {
-    let result: Vec<Vec<u8>> = data
-        .lines()
-        .map(|line| {
-            let row: Vec<u8> = line
-                .chars()
-                .map(|c| {
-                    let val: u8 = c.to_string().parse().unwrap();
-                    val
-                })
-                .collect();
-            row
-        })
-        .collect();
-
-    Grid::new(result)
+fn parse_data(data: &str) -> Result<Grid, SolveError> {
+    let mut result: Vec<Vec<u8>> = Vec::new();
+
+    for (x, line) in data.lines().enumerate() {
+        let mut row: Vec<u8> = Vec::with_capacity(line.len());
+        for (y, c) in line.chars().enumerate() {
+            let Some(val) = c.to_digit(10) else {
+                let diagnostic = Diagnostic::at_line(data, x, y, "");
+                return Err(diagnostic.expected("a height from 0 to 9").into());
+            };
+            row.push(val as u8);
+        }
+
+        if let Some(first) = result.first() {
+            if first.len() != row.len() {
+                let column = first.len().min(row.len());
+                let message = format!("expected {} heights, found {}", first.len(), row.len());
+                return Err(Diagnostic::at_line(data, x, column, message).into());
+            }
+        }
+        result.push(row);
+    }
+
+    if result.len() < 2 || result[0].len() < 2 {
+        return Err(SolveError::Validation(
+            "the map must be at least 2 by 2".to_string(),
+        ));
+    }
+    Ok(Grid::new(result))
 }
 
 #[cfg(test)]
 mod tests {
-    use input::get_puzzle_input;
-
     use super::*;
 
-    #[test]
-    fn test_part1() {
-        let input = get_puzzle_input("10-sample");
-        let result = solve_puzzle(input.as_str());
-        assert_eq!(result, 36);
+    input::sample_tests! {
+        Day10;
+        test_part1: "10-sample", part 1 => 36;
+        test_part2: "10-sample", part 2 => 81;
     }
 
     #[test]
-    fn test_part2() {
-        let input = get_puzzle_input("10-sample");
-        let result = solve_puzzle_trails(input.as_str());
-        assert_eq!(result, 81);
+    fn test_generated() {
+        generator::check_solution::<Day10>(10, 30);
     }
 }
@@ -1,32 +1,57 @@
 use std::collections::HashMap;
 
+use input::{end_of_line, parse_all, Answer, ParseResult, Solution, SolveError};
 use nom::{
     character::complete::{self, space1},
-    multi::separated_list1,
-    IResult, Parser,
+    Parser,
 };
+use nom_supreme::multi::collect_separated_terminated;
 
-pub fn part1(input: &str) -> usize {
-    solve_puzzle_cached(input, 25)
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Parsed = Vec<u64>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, SolveError> {
+        parse_data(input)
+    }
+
+    fn part1(parsed: &Self::Parsed) -> Result<Answer, SolveError> {
+        Ok(solve_puzzle_cached(parsed, 25)?.into())
+    }
+
+    fn part2(parsed: &Self::Parsed) -> Result<Answer, SolveError> {
+        Ok(solve_puzzle_cached(parsed, 75)?.into())
+    }
 }
 
-pub fn part2(input: &str) -> usize {
-    solve_puzzle_cached(input, 75)
+pub fn part1(input: &str) -> Result<Answer, SolveError> {
+    Day11::part1(&Day11::parse(input)?)
 }
 
-fn solve_puzzle_cached(data: &str, blinks: usize) -> usize {
-    let stones = parse_data(data);
+pub fn part2(input: &str) -> Result<Answer, SolveError> {
+    Day11::part2(&Day11::parse(input)?)
+}
+
+fn solve_puzzle_cached(stones: &[u64], blinks: usize) -> Result<u64, SolveError> {
     let mut cache: HashMap<(u64, usize), u64> = HashMap::new();
 
     // Now, we will use caching
-    let mut total: usize = 0;
-    for num in stones.iter() {
-        total += wink_stone(*num, blinks, &mut cache);
-    }
-    total
+    stones
+        .iter()
+        .try_fold(0_u64, |total, num| {
+            total.checked_add(wink_stone(*num, blinks, &mut cache)?)
+        })
+        .ok_or_else(|| {
+            SolveError::Overflow(format!(
+                "a stone or the stone count after {} blinks",
+                blinks
+            ))
+        })
 }
 
-fn blink_stone(num: u64, blinks: usize) -> Vec<u64> {
+/// `None` when a stone no longer fits in a u64
+fn blink_stone(num: u64, blinks: usize) -> Option<Vec<u64>> {
     let mut digits: Vec<u64> = vec![num];
     for _ in 0..blinks {
         let mut current: Vec<u64> = Vec::new();
@@ -40,43 +65,41 @@ fn blink_stone(num: u64, blinks: usize) -> Vec<u64> {
                     current.push(left);
                     current.push(right);
                 } else {
-                    current.push(*num * 2024);
+                    current.push(num.checked_mul(2024)?);
                 }
             }
         }
         digits = current;
     }
-    digits
+    Some(digits)
 }
 
-fn wink_stone(num: u64, blinks: usize, cache: &mut HashMap<(u64, usize), u64>) -> usize {
+/// Number of stones after the blinks, `None` on overflow
+fn wink_stone(num: u64, blinks: usize, cache: &mut HashMap<(u64, usize), u64>) -> Option<u64> {
     if blinks == 0 {
-        return 1;
+        return Some(1);
     }
 
     if let Some(entry) = cache.get(&(num, blinks)) {
-        return *entry as usize;
+        return Some(*entry);
     }
 
-    let mut total: usize = 0;
-    let stones = blink_stone(num, 1);
+    let mut total: u64 = 0;
+    let stones = blink_stone(num, 1)?;
     for v in stones.iter() {
-        total += wink_stone(*v, blinks - 1, cache);
+        total = total.checked_add(wink_stone(*v, blinks - 1, cache)?)?;
     }
 
-    cache.insert((num, blinks), total as u64);
-    total
+    cache.insert((num, blinks), total);
+    Some(total)
 }
 
-fn parse_data(data: &str) -> Vec<u64> {
-    if let Ok((_, items)) = parse_line(data) {
-        return items;
-    }
-    vec![]
+fn parse_data(data: &str) -> Result<Vec<u64>, SolveError> {
+    parse_all(data, parse_line)
 }
 
-fn parse_line(data: &str) -> IResult<&str, Vec<u64>> {
-    separated_list1(space1, complete::u64).parse(data)
+fn parse_line(data: &str) -> ParseResult<'_, Vec<u64>> {
+    collect_separated_terminated(complete::u64, space1, end_of_line).parse(data)
 }
 
 fn get_num_digits(num: u64) -> Vec<u64> {
@@ -116,6 +139,7 @@ fn split_digits(digits: Vec<u64>) -> (u64, u64) {
 #[cfg(test)]
 mod tests {
     use input::get_puzzle_input;
+    use proptest::prelude::*;
 
     use super::*;
 
@@ -139,17 +163,50 @@ mod tests {
         assert_eq!(split_digits(vec![1, 0]), (1, 0));
     }
 
+    input::sample_tests! {
+        Day11;
+        test_part1: "11-sample", part 1 => 55312;
+    }
+
     #[test]
-    fn test_part1() {
+    fn test_six_blinks() {
         let input = get_puzzle_input("11-sample");
-        let result = solve_puzzle_cached(input.as_str(), 6);
-        assert_eq!(result, 22);
+        let result = solve_puzzle_cached(&parse_data(input.as_str()).unwrap(), 6);
+        assert_eq!(result, Ok(22));
     }
 
     #[test]
-    fn test_part2() {
-        let input = get_puzzle_input("11-sample");
-        let result = solve_puzzle_cached(input.as_str(), 25);
-        assert_eq!(result, 55312);
+    fn test_fuzz_regressions() {
+        // Input found by `aoc fuzz`, the stone overflows after a few blinks
+        assert!(matches!(
+            part2("8833156656568"),
+            Err(SolveError::Overflow(_))
+        ));
+        assert_eq!(blink_stone(u64::MAX / 1000, 1), None);
+    }
+
+    proptest! {
+        #[test]
+        fn prop_digits_roundtrip(num in any::<u64>()) {
+            prop_assert_eq!(merge_digits(&get_num_digits(num)), num);
+        }
+
+        #[test]
+        fn prop_cached_matches_expansion(
+            stones in prop::collection::vec(0_u64..1_000_000, 1..4),
+            blinks in 0_usize..20,
+        ) {
+            // Small stones keep the naive expansion from overflowing
+            let expanded: usize = stones
+                .iter()
+                .map(|s| blink_stone(*s, blinks).unwrap().len())
+                .sum();
+            prop_assert_eq!(solve_puzzle_cached(&stones, blinks), Ok(expanded as u64));
+        }
+    }
+
+    #[test]
+    fn test_generated() {
+        generator::check_solution::<Day11>(11, 10);
     }
 }
@@ -0,0 +1,48 @@
+//! Two columns of location IDs, `size` lines
+
+use input::Answer;
+
+use crate::Rng;
+
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let mut left: Vec<u64> = Vec::with_capacity(size);
+    let mut output = String::new();
+
+    for _ in 0..size {
+        let a = rng.range(10000, 99999);
+        left.push(a);
+        // Reuse IDs from the left column so the similarity score is not zero
+        let b = if rng.chance(30) {
+            *rng.pick(&left)
+        } else {
+            rng.range(10000, 99999)
+        };
+        output.push_str(format!("{}   {}\n", a, b).as_str());
+    }
+    output
+}
+
+/// Answers by brute force, written apart from the day's solution
+pub fn reference(input: &str) -> (Option<Answer>, Option<Answer>) {
+    let (mut left, mut right): (Vec<i64>, Vec<i64>) = input
+        .lines()
+        .map(|line| {
+            let mut ids = line.split_whitespace().map(|id| id.parse::<i64>().unwrap());
+            (ids.next().unwrap(), ids.next().unwrap())
+        })
+        .unzip();
+    left.sort();
+    right.sort();
+
+    let distance: i64 = left
+        .iter()
+        .zip(right.iter())
+        .map(|(a, b)| (a - b).abs())
+        .sum();
+    let similarity: i64 = left
+        .iter()
+        .map(|a| a * right.iter().filter(|b| *b == a).count() as i64)
+        .sum();
+    (Some(distance.into()), Some(similarity.into()))
+}
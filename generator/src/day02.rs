@@ -0,0 +1,73 @@
+//! Reports of 5 to 8 levels, `size` lines, some of them unsafe
+
+use input::Answer;
+
+use crate::Rng;
+
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let mut output = String::new();
+
+    for _ in 0..size {
+        let len = rng.range(5, 8) as usize;
+        let increasing = rng.chance(50);
+        let bad_at = if rng.chance(40) {
+            Some(rng.below(len as u64) as usize)
+        } else {
+            None
+        };
+
+        let mut level = rng.range(30, 70) as i64;
+        let mut levels: Vec<i64> = Vec::with_capacity(len);
+        for k in 0..len {
+            if k > 0 {
+                // A bad level either stalls, jumps too far or turns around
+                let step = match bad_at {
+                    Some(b) if b == k => *rng.pick(&[0, 4, 5, -2]),
+                    _ => rng.range(1, 3) as i64,
+                };
+                level += if increasing { step } else { -step };
+            }
+            levels.push(level);
+        }
+
+        let line: Vec<String> = levels.iter().map(|l| l.to_string()).collect();
+        output.push_str(line.join(" ").as_str());
+        output.push('\n');
+    }
+    output
+}
+
+/// Answers by brute force, written apart from the day's solution
+pub fn reference(input: &str) -> (Option<Answer>, Option<Answer>) {
+    let reports: Vec<Vec<i64>> = input
+        .lines()
+        .map(|line| {
+            line.split_whitespace()
+                .map(|l| l.parse().unwrap())
+                .collect()
+        })
+        .collect();
+
+    let safe = reports.iter().filter(|r| is_safe(r)).count();
+    let dampened = reports
+        .iter()
+        .filter(|r| {
+            (0..r.len()).any(|skip| {
+                let rest: Vec<i64> = r
+                    .iter()
+                    .enumerate()
+                    .filter(|(k, _)| *k != skip)
+                    .map(|(_, l)| *l)
+                    .collect();
+                is_safe(&rest)
+            })
+        })
+        .count();
+    (Some(safe.into()), Some(dampened.into()))
+}
+
+fn is_safe(levels: &[i64]) -> bool {
+    let steps: Vec<i64> = levels.windows(2).map(|w| w[1] - w[0]).collect();
+    steps.iter().all(|s| (1..=3).contains(s)) || steps.iter().all(|s| (-3..=-1).contains(s))
+}
@@ -0,0 +1,74 @@
+//! Corrupted memory with `size` instructions, valid or not, between junk
+
+use input::Answer;
+
+use crate::Rng;
+
+/// Junk that can't form an instruction, there is no `d` and no digit
+const JUNK: &[u8] = b"%&!@^*()[]{}<>:;?,'+-_ xmulo";
+
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let mut output = String::new();
+
+    for _ in 0..size {
+        for _ in 0..rng.below(6) {
+            output.push(*rng.pick(JUNK) as char);
+        }
+
+        let a = rng.range(1, 999);
+        let b = rng.range(1, 999);
+        let instruction = match rng.below(10) {
+            0 => "do()".to_string(),
+            1 => "don't()".to_string(),
+            2 => format!("mul[{},{}]", a, b),
+            3 => format!("mul({} ,{})", a, b),
+            4 => format!("mul({},{}!", a, b),
+            _ => format!("mul({},{})", a, b),
+        };
+        output.push_str(instruction.as_str());
+    }
+    output.push('\n');
+    output
+}
+
+/// Answers by brute force, written apart from the day's solution
+pub fn reference(input: &str) -> (Option<Answer>, Option<Answer>) {
+    let bytes = input.as_bytes();
+    let mut enabled = true;
+    let (mut all, mut only_enabled) = (0_i64, 0_i64);
+
+    for k in 0..bytes.len() {
+        let rest = &bytes[k..];
+        if rest.starts_with(b"do()") {
+            enabled = true;
+        } else if rest.starts_with(b"don't()") {
+            enabled = false;
+        } else if let Some(product) = rest.strip_prefix(b"mul(").and_then(read_mul) {
+            all += product;
+            if enabled {
+                only_enabled += product;
+            }
+        }
+    }
+    (Some(all.into()), Some(only_enabled.into()))
+}
+
+/// Reads `X,Y)` where both numbers have 1 to 3 digits
+fn read_mul(rest: &[u8]) -> Option<i64> {
+    let (x, rest) = read_number(rest)?;
+    let rest = rest.strip_prefix(b",")?;
+    let (y, rest) = read_number(rest)?;
+    rest.starts_with(b")").then_some(x * y)
+}
+
+fn read_number(rest: &[u8]) -> Option<(i64, &[u8])> {
+    let digits = rest.iter().take_while(|b| b.is_ascii_digit()).count();
+    if !(1..=3).contains(&digits) {
+        return None;
+    }
+    let value = rest[..digits]
+        .iter()
+        .fold(0, |n, d| n * 10 + (d - b'0') as i64);
+    Some((value, &rest[digits..]))
+}
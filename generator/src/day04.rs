@@ -0,0 +1,58 @@
+//! Word search of `size` by `size` letters
+
+use input::Answer;
+
+use crate::Rng;
+
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let mut output = String::new();
+
+    for _ in 0..size {
+        for _ in 0..size {
+            output.push(*rng.pick(b"XMAS") as char);
+        }
+        output.push('\n');
+    }
+    output
+}
+
+/// Answers by brute force, written apart from the day's solution
+pub fn reference(input: &str) -> (Option<Answer>, Option<Answer>) {
+    let grid: Vec<&[u8]> = input.lines().map(|l| l.as_bytes()).collect();
+    let at = |x: i64, y: i64| -> u8 {
+        if x < 0 || y < 0 || x >= grid.len() as i64 || y >= grid[x as usize].len() as i64 {
+            return b' ';
+        }
+        grid[x as usize][y as usize]
+    };
+
+    let mut words = 0;
+    let mut crosses = 0;
+    for x in 0..grid.len() as i64 {
+        for y in 0..grid[x as usize].len() as i64 {
+            for (dx, dy) in [
+                (-1, -1),
+                (-1, 0),
+                (-1, 1),
+                (0, -1),
+                (0, 1),
+                (1, -1),
+                (1, 0),
+                (1, 1),
+            ] {
+                if (0..4).all(|k| at(x + dx * k, y + dy * k) == b"XMAS"[k as usize]) {
+                    words += 1;
+                }
+            }
+
+            let diagonal = [at(x - 1, y - 1), at(x + 1, y + 1)];
+            let anti_diagonal = [at(x - 1, y + 1), at(x + 1, y - 1)];
+            let is_mas = |pair: [u8; 2]| pair == [b'M', b'S'] || pair == [b'S', b'M'];
+            if at(x, y) == b'A' && is_mas(diagonal) && is_mas(anti_diagonal) {
+                crosses += 1;
+            }
+        }
+    }
+    (Some(words.into()), Some(crosses.into()))
+}
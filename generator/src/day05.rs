@@ -0,0 +1,78 @@
+//! Ordering rules for 49 pages and `size` updates, about half of them in order
+
+use std::collections::HashSet;
+
+use input::Answer;
+
+use crate::Rng;
+
+const PAGES: usize = 49;
+
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+
+    // A random total order, every pair of pages gets a rule like the real input
+    let mut order: Vec<u64> = (10..100).collect();
+    rng.shuffle(&mut order);
+    order.truncate(PAGES);
+
+    let mut rules: Vec<(u64, u64)> = Vec::new();
+    for i in 0..order.len() {
+        for j in (i + 1)..order.len() {
+            rules.push((order[i], order[j]));
+        }
+    }
+    rng.shuffle(&mut rules);
+
+    let mut output = String::new();
+    for (a, b) in rules.iter() {
+        output.push_str(format!("{}|{}\n", a, b).as_str());
+    }
+    output.push('\n');
+
+    for _ in 0..size {
+        let len = rng.range(2, 11) as usize * 2 + 1;
+        let mut positions: Vec<usize> = (0..order.len()).collect();
+        rng.shuffle(&mut positions);
+        positions.truncate(len);
+        if rng.chance(50) {
+            positions.sort();
+        }
+
+        let update: Vec<String> = positions.iter().map(|k| order[*k].to_string()).collect();
+        output.push_str(update.join(",").as_str());
+        output.push('\n');
+    }
+    output
+}
+
+/// Answers by brute force, written apart from the day's solution
+pub fn reference(input: &str) -> (Option<Answer>, Option<Answer>) {
+    let (rules, updates) = input.split_once("\n\n").unwrap();
+    let rules: HashSet<(u64, u64)> = rules
+        .lines()
+        .map(|line| {
+            let (a, b) = line.split_once('|').unwrap();
+            (a.parse().unwrap(), b.parse().unwrap())
+        })
+        .collect();
+
+    let (mut ordered, mut reordered) = (0, 0);
+    for line in updates.lines() {
+        let pages: Vec<u64> = line.split(',').map(|p| p.parse().unwrap()).collect();
+        let in_order = (0..pages.len())
+            .all(|i| (i + 1..pages.len()).all(|j| !rules.contains(&(pages[j], pages[i]))));
+        if in_order {
+            ordered += pages[pages.len() / 2];
+            continue;
+        }
+
+        // The middle page has as many pages that must come before it as after it
+        let middle = pages.iter().find(|p| {
+            let before = pages.iter().filter(|q| rules.contains(&(**q, **p))).count();
+            before == pages.len() / 2
+        });
+        reordered += middle.unwrap();
+    }
+    (Some(ordered.into()), Some(reordered.into()))
+}
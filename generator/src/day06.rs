@@ -0,0 +1,114 @@
+//! Lab map of `size` by `size` with a guard that eventually walks out
+
+use std::collections::HashSet;
+
+use input::Answer;
+
+use crate::Rng;
+
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let size = size.max(2);
+
+    loop {
+        let mut grid: Vec<Vec<u8>> = (0..size)
+            .map(|_| {
+                (0..size)
+                    .map(|_| if rng.chance(12) { b'#' } else { b'.' })
+                    .collect()
+            })
+            .collect();
+
+        let row = rng.below(size as u64) as usize;
+        let col = rng.below(size as u64) as usize;
+        grid[row][col] = b'^';
+
+        // Part 1 never ends when the guard is stuck in a loop, try again
+        if walks_out(&grid, row, col) {
+            let mut output = String::new();
+            for line in grid.iter() {
+                output.push_str(String::from_utf8_lossy(line).as_ref());
+                output.push('\n');
+            }
+            return output;
+        }
+    }
+}
+
+fn walks_out(grid: &[Vec<u8>], row: usize, col: usize) -> bool {
+    const DIRS: [(i64, i64); 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+    let size = grid.len() as i64;
+    let mut seen: HashSet<(i64, i64, usize)> = HashSet::new();
+    let (mut r, mut c, mut d) = (row as i64, col as i64, 0);
+
+    while seen.insert((r, c, d)) {
+        let (nr, nc) = (r + DIRS[d].0, c + DIRS[d].1);
+        if nr < 0 || nc < 0 || nr >= size || nc >= size {
+            return true;
+        }
+        if grid[nr as usize][nc as usize] == b'#' {
+            d = (d + 1) % 4;
+        } else {
+            (r, c) = (nr, nc);
+        }
+    }
+    false
+}
+
+/// Largest map solved for part 2, trying every obstruction is quadratic in the number of cells
+const MAX_CELLS_PART2: usize = 2500;
+
+/// Answers by brute force, written apart from the day's solution
+pub fn reference(input: &str) -> (Option<Answer>, Option<Answer>) {
+    let mut grid: Vec<Vec<u8>> = input.lines().map(|l| l.as_bytes().to_vec()).collect();
+    let (row, col) = (0..grid.len())
+        .flat_map(|r| (0..grid[r].len()).map(move |c| (r, c)))
+        .find(|(r, c)| grid[*r][*c] == b'^')
+        .unwrap();
+
+    let Some(path) = patrol(&grid, row, col) else {
+        return (None, None);
+    };
+    let part1 = Some(path.len().into());
+
+    let cells: usize = grid.iter().map(|r| r.len()).sum();
+    if cells > MAX_CELLS_PART2 {
+        return (part1, None);
+    }
+    let mut loops = 0;
+    for (r, c) in path.iter() {
+        if (*r, *c) == (row, col) {
+            continue;
+        }
+        grid[*r][*c] = b'#';
+        if patrol(&grid, row, col).is_none() {
+            loops += 1;
+        }
+        grid[*r][*c] = b'.';
+    }
+    (part1, Some(loops.into()))
+}
+
+/// Cells the guard walks through going up from the start, `None` if it never leaves
+fn patrol(grid: &[Vec<u8>], row: usize, col: usize) -> Option<HashSet<(usize, usize)>> {
+    const DIRS: [(i64, i64); 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+    let mut seen: HashSet<(i64, i64, usize)> = HashSet::new();
+    let (mut r, mut c, mut d) = (row as i64, col as i64, 0);
+
+    while seen.insert((r, c, d)) {
+        let (nr, nc) = (r + DIRS[d].0, c + DIRS[d].1);
+        if nr < 0 || nc < 0 || nr >= grid.len() as i64 || nc >= grid[0].len() as i64 {
+            return Some(
+                seen.iter()
+                    .map(|(r, c, _)| (*r as usize, *c as usize))
+                    .collect(),
+            );
+        }
+        if grid[nr as usize][nc as usize] == b'#' {
+            d = (d + 1) % 4;
+        } else {
+            (r, c) = (nr, nc);
+        }
+    }
+    None
+}
@@ -0,0 +1,68 @@
+//! `size` calibration equations of 2 to 7 numbers, some of them unsolvable
+
+use input::Answer;
+
+use crate::Rng;
+
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let mut output = String::new();
+
+    for _ in 0..size {
+        let count = rng.range(2, 7) as usize;
+        let numbers: Vec<u64> = (0..count).map(|_| rng.range(1, 99)).collect();
+
+        // Every step grows the value 100 times at most, 7 numbers fit in an i64
+        let mut result = numbers[0];
+        for n in numbers.iter().skip(1) {
+            result = match rng.below(3) {
+                0 => result + n,
+                1 => result * n,
+                _ => format!("{}{}", result, n).parse().unwrap(),
+            };
+        }
+        if rng.chance(30) {
+            result += 1;
+        }
+
+        let line: Vec<String> = numbers.iter().map(|n| n.to_string()).collect();
+        output.push_str(format!("{}: {}\n", result, line.join(" ")).as_str());
+    }
+    output
+}
+
+/// Answers by brute force, written apart from the day's solution
+pub fn reference(input: &str) -> (Option<Answer>, Option<Answer>) {
+    let (mut two_ops, mut three_ops) = (0_u64, 0_u64);
+    for line in input.lines() {
+        let (result, numbers) = line.split_once(": ").unwrap();
+        let result: u64 = result.parse().unwrap();
+        let numbers: Vec<u64> = numbers.split(' ').map(|n| n.parse().unwrap()).collect();
+
+        if solvable(result, &numbers, 2) {
+            two_ops += result;
+        }
+        if solvable(result, &numbers, 3) {
+            three_ops += result;
+        }
+    }
+    (Some(two_ops.into()), Some(three_ops.into()))
+}
+
+/// Tries every combination of the first `ops` of add, multiply and concatenate
+fn solvable(result: u64, numbers: &[u64], ops: u64) -> bool {
+    let slots = numbers.len() as u32 - 1;
+    (0..ops.pow(slots)).any(|combination| {
+        let mut code = combination;
+        let mut value = numbers[0];
+        for n in numbers.iter().skip(1) {
+            value = match code % ops {
+                0 => value + n,
+                1 => value * n,
+                _ => value * 10_u64.pow(n.to_string().len() as u32) + n,
+            };
+            code /= ops;
+        }
+        value == result
+    })
+}
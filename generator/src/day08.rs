@@ -0,0 +1,65 @@
+//! Antenna map of `size` by `size`, about one antenna every 25 cells
+
+use std::collections::{HashMap, HashSet};
+
+use input::Answer;
+
+use crate::Rng;
+
+const FREQUENCIES: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let kinds = (size / 5).clamp(1, FREQUENCIES.len());
+    let mut output = String::new();
+
+    for _ in 0..size {
+        for _ in 0..size {
+            let cell = if rng.chance(4) {
+                FREQUENCIES[rng.below(kinds as u64) as usize]
+            } else {
+                b'.'
+            };
+            output.push(cell as char);
+        }
+        output.push('\n');
+    }
+    output
+}
+
+/// Answers by brute force, written apart from the day's solution
+pub fn reference(input: &str) -> (Option<Answer>, Option<Answer>) {
+    let rows = input.lines().count() as i64;
+    let cols = input.lines().next().map_or(0, |l| l.len()) as i64;
+    let inside = |(x, y): (i64, i64)| x >= 0 && y >= 0 && x < rows && y < cols;
+
+    let mut antennas: HashMap<char, Vec<(i64, i64)>> = HashMap::new();
+    for (x, line) in input.lines().enumerate() {
+        for (y, c) in line.chars().enumerate() {
+            if c.is_ascii_alphanumeric() {
+                antennas.entry(c).or_default().push((x as i64, y as i64));
+            }
+        }
+    }
+
+    let mut antinodes: HashSet<(i64, i64)> = HashSet::new();
+    let mut harmonics: HashSet<(i64, i64)> = HashSet::new();
+    for positions in antennas.values() {
+        for a in positions.iter() {
+            for b in positions.iter().filter(|b| *b != a) {
+                // Beyond b, steps the size of the distance between the pair like the solutions do
+                let step = (b.0 - a.0, b.1 - a.1);
+                let next = (b.0 + step.0, b.1 + step.1);
+                if inside(next) {
+                    antinodes.insert(next);
+                }
+                let mut pos = *b;
+                while inside(pos) {
+                    harmonics.insert(pos);
+                    pos = (pos.0 + step.0, pos.1 + step.1);
+                }
+            }
+        }
+    }
+    (Some(antinodes.len().into()), Some(harmonics.len().into()))
+}
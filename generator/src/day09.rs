@@ -0,0 +1,84 @@
+//! Disk map of `size` digits, alternating files and free space
+
+use input::Answer;
+
+use crate::Rng;
+
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let mut output = String::new();
+
+    for k in 0..size {
+        // Files take at least one block, free space may be empty
+        let digit = if k % 2 == 0 {
+            rng.range(1, 9)
+        } else {
+            rng.range(0, 9)
+        };
+        output.push_str(digit.to_string().as_str());
+    }
+    output.push('\n');
+    output
+}
+
+/// Longest disk map solved for part 2, every file scans the whole disk for free space
+const MAX_DIGITS_PART2: usize = 5000;
+
+/// Answers by brute force, written apart from the day's solution
+pub fn reference(input: &str) -> (Option<Answer>, Option<Answer>) {
+    let digits: Vec<usize> = input.trim().bytes().map(|b| (b - b'0') as usize).collect();
+
+    let mut disk: Vec<Option<usize>> = Vec::new();
+    for (k, len) in digits.iter().enumerate() {
+        let block = if k % 2 == 0 { Some(k / 2) } else { None };
+        disk.extend(std::iter::repeat_n(block, *len));
+    }
+
+    // Part 1 moves single blocks from the end into the first free block
+    let mut blocks = disk.clone();
+    let (mut free, mut last) = (0, blocks.len());
+    loop {
+        while free < blocks.len() && blocks[free].is_some() {
+            free += 1;
+        }
+        while last > 0 && blocks[last - 1].is_none() {
+            last -= 1;
+        }
+        if last == 0 || free >= last - 1 {
+            break;
+        }
+        blocks.swap(free, last - 1);
+    }
+    let part1 = Some(checksum(&blocks).into());
+
+    if digits.len() > MAX_DIGITS_PART2 {
+        return (part1, None);
+    }
+
+    // Part 2 moves whole files, highest id first, into the leftmost span that fits
+    let files = digits.len().div_ceil(2);
+    for id in (0..files).rev() {
+        let start = disk.iter().position(|b| *b == Some(id)).unwrap();
+        let len = digits[id * 2];
+        let mut span = 0;
+        for k in 0..start {
+            span = if disk[k].is_none() { span + 1 } else { 0 };
+            if span == len {
+                let target = k + 1 - len;
+                for i in 0..len {
+                    disk.swap(target + i, start + i);
+                }
+                break;
+            }
+        }
+    }
+    (part1, Some(checksum(&disk).into()))
+}
+
+fn checksum(blocks: &[Option<usize>]) -> u64 {
+    blocks
+        .iter()
+        .enumerate()
+        .map(|(k, b)| b.map_or(0, |id| (k * id) as u64))
+        .sum()
+}
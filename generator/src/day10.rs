@@ -0,0 +1,66 @@
+//! Topographic map of `size` by `size`, diagonal slopes with random noise
+
+use std::collections::HashSet;
+
+use input::Answer;
+
+use crate::Rng;
+
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let mut output = String::new();
+
+    for x in 0..size {
+        for y in 0..size {
+            let height = if rng.chance(25) {
+                rng.below(10)
+            } else {
+                ((x + y) % 10) as u64
+            };
+            output.push_str(height.to_string().as_str());
+        }
+        output.push('\n');
+    }
+    output
+}
+
+/// Answers by brute force, written apart from the day's solution
+pub fn reference(input: &str) -> (Option<Answer>, Option<Answer>) {
+    let grid: Vec<Vec<u8>> = input
+        .lines()
+        .map(|l| l.bytes().map(|b| b - b'0').collect())
+        .collect();
+
+    let (mut score, mut rating) = (0, 0);
+    for x in 0..grid.len() {
+        for y in 0..grid[x].len() {
+            if grid[x][y] == 0 {
+                let mut peaks: HashSet<(usize, usize)> = HashSet::new();
+                rating += walk(&grid, x, y, &mut peaks);
+                score += peaks.len();
+            }
+        }
+    }
+    (Some(score.into()), Some(rating.into()))
+}
+
+/// Counts the trails from the cell to a 9, collecting the 9s reached
+fn walk(grid: &[Vec<u8>], x: usize, y: usize, peaks: &mut HashSet<(usize, usize)>) -> usize {
+    let height = grid[x][y];
+    if height == 9 {
+        peaks.insert((x, y));
+        return 1;
+    }
+
+    let mut trails = 0;
+    for (dx, dy) in [(-1, 0), (1, 0), (0, -1), (0, 1)] {
+        let (nx, ny) = (x as i64 + dx, y as i64 + dy);
+        if nx < 0 || ny < 0 || nx >= grid.len() as i64 || ny >= grid[0].len() as i64 {
+            continue;
+        }
+        if grid[nx as usize][ny as usize] == height + 1 {
+            trails += walk(grid, nx as usize, ny as usize, peaks);
+        }
+    }
+    trails
+}
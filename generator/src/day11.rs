@@ -0,0 +1,56 @@
+//! A line of `size` stones
+
+use std::collections::HashMap;
+
+use input::Answer;
+
+use crate::Rng;
+
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let stones: Vec<String> = (0..size.max(1))
+        .map(|_| rng.range(0, 99999).to_string())
+        .collect();
+    format!("{}\n", stones.join(" "))
+}
+
+/// Answers by counting stones of each value blink after blink,
+/// written apart from the day's solution
+pub fn reference(input: &str) -> (Option<Answer>, Option<Answer>) {
+    let mut stones: HashMap<u64, u64> = HashMap::new();
+    for stone in input.split_whitespace() {
+        *stones.entry(stone.parse().unwrap()).or_default() += 1;
+    }
+
+    let mut answers = (None, None);
+    for blink in 1..=75 {
+        let mut next: HashMap<u64, u64> = HashMap::new();
+        for (stone, count) in stones.iter() {
+            let digits = stone.to_string();
+            let changed = if *stone == 0 {
+                vec![1]
+            } else if digits.len() % 2 == 0 {
+                let (left, right) = digits.split_at(digits.len() / 2);
+                vec![left.parse().unwrap(), right.parse().unwrap()]
+            } else {
+                match stone.checked_mul(2024) {
+                    Some(v) => vec![v],
+                    // Too big for the solutions too, leave the answers out
+                    None => return answers,
+                }
+            };
+            for v in changed {
+                *next.entry(v).or_default() += count;
+            }
+        }
+        stones = next;
+
+        let total: u64 = stones.values().sum();
+        match blink {
+            25 => answers.0 = Some(total.into()),
+            75 => answers.1 = Some(total.into()),
+            _ => {}
+        }
+    }
+    answers
+}
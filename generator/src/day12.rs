@@ -0,0 +1,82 @@
+//! Garden of `size` by `size` with plots grown from their neighbours
+
+use input::Answer;
+
+use crate::Rng;
+
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let mut grid: Vec<Vec<u8>> = vec![vec![b'A'; size]; size];
+
+    for x in 0..size {
+        for y in 0..size {
+            // Copying a neighbour grows regions instead of single plots
+            grid[x][y] = match rng.below(10) {
+                0..=2 if x > 0 => grid[x - 1][y],
+                3..=5 if y > 0 => grid[x][y - 1],
+                _ => *rng.pick(b"ABCDEFGH"),
+            };
+        }
+    }
+
+    let mut output = String::new();
+    for line in grid.iter() {
+        output.push_str(String::from_utf8_lossy(line).as_ref());
+        output.push('\n');
+    }
+    output
+}
+
+/// Answers by brute force, written apart from the day's solution
+pub fn reference(input: &str) -> (Option<Answer>, Option<Answer>) {
+    let grid: Vec<&[u8]> = input.lines().map(|l| l.as_bytes()).collect();
+    let at = |x: i64, y: i64| -> u8 {
+        if x < 0 || y < 0 || x >= grid.len() as i64 || y >= grid[0].len() as i64 {
+            return 0;
+        }
+        grid[x as usize][y as usize]
+    };
+
+    let mut region: Vec<Vec<Option<usize>>> = vec![vec![None; grid[0].len()]; grid.len()];
+    let mut stats: Vec<(usize, usize, usize)> = Vec::new();
+    for x in 0..grid.len() {
+        for y in 0..grid[0].len() {
+            if region[x][y].is_some() {
+                continue;
+            }
+
+            // Flood fill, a side starts at every corner of the region
+            let id = stats.len();
+            let (mut area, mut perimeter, mut corners) = (0, 0, 0);
+            let mut stack = vec![(x as i64, y as i64)];
+            region[x][y] = Some(id);
+            while let Some((cx, cy)) = stack.pop() {
+                let plant = at(cx, cy);
+                area += 1;
+                for (dx, dy) in [(-1, 0), (0, 1), (1, 0), (0, -1)] {
+                    let (nx, ny) = (cx + dx, cy + dy);
+                    if at(nx, ny) != plant {
+                        perimeter += 1;
+                    } else if region[nx as usize][ny as usize].is_none() {
+                        region[nx as usize][ny as usize] = Some(id);
+                        stack.push((nx, ny));
+                    }
+
+                    // Corner between this direction and the next one clockwise
+                    let (ex, ey) = (-dy, dx);
+                    let side_a = at(cx + dx, cy + dy) == plant;
+                    let side_b = at(cx + ex, cy + ey) == plant;
+                    let diagonal = at(cx + dx + ex, cy + dy + ey) == plant;
+                    if (!side_a && !side_b) || (side_a && side_b && !diagonal) {
+                        corners += 1;
+                    }
+                }
+            }
+            stats.push((area, perimeter, corners));
+        }
+    }
+
+    let price: usize = stats.iter().map(|(a, p, _)| a * p).sum();
+    let discounted: usize = stats.iter().map(|(a, _, s)| a * s).sum();
+    (Some(price.into()), Some(discounted.into()))
+}
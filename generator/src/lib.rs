@@ -0,0 +1,238 @@
+//! Deterministic synthetic puzzle inputs.
+//!
+//! Every day has a `generate(seed, size)` function, the same seed and size
+//! always give the same input. What the size means depends on the day,
+//! ie: the number of lines or the side of a grid.
+//!
+//! Every day also has a `reference(input)` brute force solver, written apart from the
+//! day's solution so that both can be checked against each other on generated inputs.
+
+mod rng;
+
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+
+use input::{Answer, Solution};
+
+pub use rng::Rng;
+
+/// Seed used by the benches
+pub const BENCH_SEED: u64 = 2024;
+
+/// Input for any day, `None` for days without a generator
+pub fn generate(day: u8, seed: u64, size: usize) -> Option<String> {
+    let generate = match day {
+        1 => day01::generate,
+        2 => day02::generate,
+        3 => day03::generate,
+        4 => day04::generate,
+        5 => day05::generate,
+        6 => day06::generate,
+        7 => day07::generate,
+        8 => day08::generate,
+        9 => day09::generate,
+        10 => day10::generate,
+        11 => day11::generate,
+        12 => day12::generate,
+        _ => return None,
+    };
+    Some(generate(seed, size))
+}
+
+/// A generated input with the answers of the reference solver
+#[derive(Debug, Clone, PartialEq)]
+pub struct Generated {
+    pub input: String,
+    /// `None` when the reference solver is too slow for the input or gives up
+    pub part1: Option<Answer>,
+    pub part2: Option<Answer>,
+}
+
+/// Input for any day with its expected answers, `None` for days without a generator
+pub fn generate_solved(day: u8, seed: u64, size: usize) -> Option<Generated> {
+    let input = generate(day, seed, size)?;
+    let (part1, part2) = reference(day, &input)?;
+    Some(Generated {
+        input,
+        part1,
+        part2,
+    })
+}
+
+/// Answers of the reference solver for any day, `None` for days without one
+pub fn reference(day: u8, input: &str) -> Option<(Option<Answer>, Option<Answer>)> {
+    let reference = match day {
+        1 => day01::reference,
+        2 => day02::reference,
+        3 => day03::reference,
+        4 => day04::reference,
+        5 => day05::reference,
+        6 => day06::reference,
+        7 => day07::reference,
+        8 => day08::reference,
+        9 => day09::reference,
+        10 => day10::reference,
+        11 => day11::reference,
+        12 => day12::reference,
+        _ => return None,
+    };
+    Some(reference(input))
+}
+
+/// Seeds of the inputs used by `check_solution`
+const CHECK_SEEDS: u64 = 4;
+
+/// Asserts that a day's solution agrees with the reference solver on a few generated inputs
+pub fn check_solution<S: Solution>(day: u8, size: usize) {
+    for seed in 0..CHECK_SEEDS {
+        let generated = generate_solved(day, seed, size).expect("day without a generator");
+        let parsed = S::parse(&generated.input)
+            .unwrap_or_else(|err| panic!("day {} seed {} size {}: {}", day, seed, size, err));
+
+        let parts = [
+            (1, generated.part1, S::part1 as fn(&S::Parsed) -> _),
+            (2, generated.part2, S::part2),
+        ];
+        for (part, expected, solve) in parts {
+            let Some(expected) = expected else {
+                continue;
+            };
+            let answer = solve(&parsed).unwrap_or_else(|err| {
+                panic!(
+                    "day {} part {} seed {} size {}: {}",
+                    day, part, seed, size, err
+                )
+            });
+            assert_eq!(
+                answer, expected,
+                "day {} part {} seed {} size {}",
+                day, part, seed, size
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use input::get_puzzle_input;
+
+    use super::*;
+
+    #[test]
+    fn test_deterministic() {
+        assert_eq!(day05::generate(7, 10), day05::generate(7, 10));
+        assert_ne!(day05::generate(7, 10), day05::generate(8, 10));
+        assert_eq!(day12::generate(7, 20), day12::generate(7, 20));
+    }
+
+    #[test]
+    fn test_generate_any_day() {
+        assert_eq!(generate(7, 3, 10), Some(day07::generate(3, 10)));
+        assert_eq!(generate(0, 3, 10), None);
+        assert_eq!(generate(13, 3, 10), None);
+    }
+
+    #[test]
+    fn test_line_counts() {
+        assert_eq!(day01::generate(1, 25).lines().count(), 25);
+        assert_eq!(day02::generate(1, 25).lines().count(), 25);
+        assert_eq!(day07::generate(1, 25).lines().count(), 25);
+        assert_eq!(day11::generate(1, 25).split_whitespace().count(), 25);
+    }
+
+    #[test]
+    fn test_grids() {
+        for grid in [
+            day04::generate(3, 12),
+            day06::generate(3, 12),
+            day08::generate(3, 12),
+            day10::generate(3, 12),
+            day12::generate(3, 12),
+        ] {
+            let lines: Vec<&str> = grid.lines().collect();
+            assert_eq!(lines.len(), 12);
+            assert!(lines.iter().all(|l| l.len() == 12));
+        }
+        assert_eq!(day06::generate(3, 12).matches('^').count(), 1);
+    }
+
+    #[test]
+    fn test_disk_map() {
+        let map = day09::generate(5, 101);
+        assert_eq!(map.trim_end().len(), 101);
+        assert!(map
+            .trim_end()
+            .chars()
+            .step_by(2)
+            .all(|c| ('1'..='9').contains(&c)));
+    }
+
+    #[test]
+    fn test_reference_samples() {
+        let samples = [
+            (1, "01-sample", 11, 31),
+            (2, "02-sample", 2, 4),
+            (4, "04-sample", 18, 9),
+            (5, "05-sample", 143, 123),
+            (6, "06-sample", 41, 6),
+            (7, "07-sample", 3749, 11387),
+            (8, "08-sample", 14, 34),
+            (9, "09-sample", 1928, 2858),
+            (10, "10-sample", 36, 81),
+            (12, "12-sample", 140, 80),
+        ];
+        for (day, file, part1, part2) in samples {
+            let input = get_puzzle_input(file);
+            assert_eq!(
+                reference(day, &input),
+                Some((Some(part1.into()), Some(part2.into()))),
+                "{}",
+                file
+            );
+        }
+
+        assert_eq!(
+            day03::reference(&get_puzzle_input("03-sample1")).0,
+            Some(161.into())
+        );
+        assert_eq!(
+            day03::reference(&get_puzzle_input("03-sample2")).1,
+            Some(48.into())
+        );
+        for (sample, part2) in [
+            ("12-sample2", 436),
+            ("12-sample3", 1206),
+            ("12-sample4", 236),
+            ("12-sample5", 368),
+        ] {
+            assert_eq!(
+                day12::reference(&get_puzzle_input(sample)).1,
+                Some(part2.into()),
+                "{}",
+                sample
+            );
+        }
+        assert_eq!(
+            day11::reference(&get_puzzle_input("11-sample")).0,
+            Some(55312.into())
+        );
+    }
+
+    #[test]
+    fn test_generate_solved() {
+        let generated = generate_solved(2, 3, 10).unwrap();
+        assert_eq!(generated.input, day02::generate(3, 10));
+        assert!(generated.part1.is_some() && generated.part2.is_some());
+        assert_eq!(generate_solved(13, 3, 10), None);
+    }
+}
@@ -0,0 +1,89 @@
+/// Small deterministic random number generator (SplitMix64).
+///
+/// Not suitable for anything but generating test inputs, the point is that
+/// the same seed gives the same input on every machine and every run.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Random value in `0..n`, `n` must not be zero
+    pub fn below(&mut self, n: u64) -> u64 {
+        assert!(n > 0, "Upper bound must not be zero");
+        // Multiply and shift, the bias is irrelevant for test inputs
+        ((self.next_u64() as u128 * n as u128) >> 64) as u64
+    }
+
+    /// Random value in `low..=high`
+    pub fn range(&mut self, low: u64, high: u64) -> u64 {
+        low + self.below(high - low + 1)
+    }
+
+    /// True with a probability of `percent` out of 100
+    pub fn chance(&mut self, percent: u64) -> bool {
+        self.below(100) < percent
+    }
+
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len() as u64) as usize]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.below(i as u64 + 1) as usize;
+            items.swap(i, j);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deterministic() {
+        let a: Vec<u64> = {
+            let mut rng = Rng::new(42);
+            (0..5).map(|_| rng.next_u64()).collect()
+        };
+        let b: Vec<u64> = {
+            let mut rng = Rng::new(42);
+            (0..5).map(|_| rng.next_u64()).collect()
+        };
+        assert_eq!(a, b);
+        assert_ne!(Rng::new(1).next_u64(), Rng::new(2).next_u64());
+    }
+
+    #[test]
+    fn test_range() {
+        let mut rng = Rng::new(7);
+        for _ in 0..1000 {
+            let v = rng.range(3, 5);
+            assert!((3..=5).contains(&v));
+        }
+        assert_eq!(rng.range(9, 9), 9);
+    }
+
+    #[test]
+    fn test_shuffle() {
+        let mut rng = Rng::new(7);
+        let mut items: Vec<u32> = (0..20).collect();
+        rng.shuffle(&mut items);
+        assert_ne!(items, (0..20).collect::<Vec<u32>>());
+        items.sort();
+        assert_eq!(items, (0..20).collect::<Vec<u32>>());
+    }
+}
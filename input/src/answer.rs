@@ -0,0 +1,146 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// A puzzle answer, either a number of any width or a text
+#[derive(Debug, Clone)]
+pub enum Answer {
+    Signed(i64),
+    Unsigned(u64),
+    Big(i128),
+    Text(String),
+}
+
+impl Answer {
+    /// Numeric value of the answer, if it is a number
+    fn numeric(&self) -> Option<i128> {
+        match self {
+            Answer::Signed(v) => Some(*v as i128),
+            Answer::Unsigned(v) => Some(*v as i128),
+            Answer::Big(v) => Some(*v),
+            Answer::Text(_) => None,
+        }
+    }
+
+    /// Checks the answer against a recorded one, ie: from an answers file
+    pub fn matches(&self, recorded: &str) -> bool {
+        match recorded.trim().parse::<Answer>() {
+            Ok(expected) => *self == expected,
+            Err(_) => false,
+        }
+    }
+}
+
+impl PartialEq for Answer {
+    fn eq(&self, other: &Self) -> bool {
+        // Numbers are equal regardless of the width they are stored in
+        match (self.numeric(), other.numeric()) {
+            (Some(a), Some(b)) => a == b,
+            _ => self.to_string() == other.to_string(),
+        }
+    }
+}
+
+impl Eq for Answer {}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Signed(v) => write!(f, "{}", v),
+            Answer::Unsigned(v) => write!(f, "{}", v),
+            Answer::Big(v) => write!(f, "{}", v),
+            Answer::Text(v) => write!(f, "{}", v),
+        }
+    }
+}
+
+impl FromStr for Answer {
+    type Err = String;
+
+    /// Reads numbers into the narrowest variant and anything else as text
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if value.is_empty() {
+            return Err("Answer must not be empty".to_string());
+        }
+        if let Ok(v) = value.parse::<i64>() {
+            return Ok(Answer::Signed(v));
+        }
+        if let Ok(v) = value.parse::<u64>() {
+            return Ok(Answer::Unsigned(v));
+        }
+        if let Ok(v) = value.parse::<i128>() {
+            return Ok(Answer::Big(v));
+        }
+        Ok(Answer::Text(value.to_string()))
+    }
+}
+
+macro_rules! answer_from {
+    ($variant:ident, $wide:ty, $($t:ty),+) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self {
+                    Answer::$variant(value as $wide)
+                }
+            }
+        )+
+    };
+}
+
+answer_from!(Signed, i64, i8, i16, i32, i64, isize);
+answer_from!(Unsigned, u64, u8, u16, u32, u64, usize);
+answer_from!(Big, i128, i128);
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::Text(value.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_answer_from() {
+        assert_eq!(Answer::from(42_i32), Answer::Signed(42));
+        assert_eq!(Answer::from(42_usize), Answer::Unsigned(42));
+        assert_eq!(Answer::from("1,2,3"), Answer::Text("1,2,3".to_string()));
+    }
+
+    #[test]
+    fn test_answer_eq_across_widths() {
+        assert_eq!(Answer::Signed(42), Answer::Unsigned(42));
+        assert_eq!(Answer::Big(42), Answer::Unsigned(42));
+        assert_ne!(Answer::Signed(-1), Answer::Unsigned(u64::MAX));
+        assert_ne!(Answer::Signed(42), Answer::Text("forty two".to_string()));
+    }
+
+    #[test]
+    fn test_answer_display() {
+        assert_eq!(Answer::Signed(-7).to_string(), "-7".to_string());
+        assert_eq!(
+            Answer::Big(i128::MAX).to_string(),
+            "170141183460469231731687303715884105727".to_string()
+        );
+        assert_eq!(
+            Answer::Text("6,5".to_string()).to_string(),
+            "6,5".to_string()
+        );
+    }
+
+    #[test]
+    fn test_answer_matches() {
+        assert!(Answer::from(3749_i64).matches("3749"));
+        assert!(Answer::from(3749_i64).matches(" 3749\n"));
+        assert!(Answer::from(u64::MAX).matches("18446744073709551615"));
+        assert!(Answer::from("35,47").matches("35,47"));
+        assert!(!Answer::from(3749_i64).matches("3750"));
+        assert!(!Answer::from(3749_i64).matches(""));
+    }
+}
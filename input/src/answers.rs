@@ -0,0 +1,80 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+
+use crate::data_dir;
+
+/// Answers recorded for the puzzle inputs, read from `data/answers.txt`.
+///
+/// Each line holds the input name, the part and the answer, ie: `07 1 3749`
+/// or `12-sample2 2 436`. Blank lines and lines starting with `#` are skipped.
+#[derive(Debug, Default)]
+pub struct Answers {
+    entries: HashMap<(String, u8), String>,
+}
+
+impl Answers {
+    pub fn parse(data: &str) -> Result<Self, String> {
+        let mut entries: HashMap<(String, u8), String> = HashMap::new();
+
+        for (k, line) in data.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let mut columns = line.splitn(3, char::is_whitespace);
+            let (Some(name), Some(part), Some(answer)) =
+                (columns.next(), columns.next(), columns.next())
+            else {
+                return Err(format!("Line {}: expected <input> <part> <answer>", k + 1));
+            };
+            let part: u8 = match part.parse() {
+                Ok(p @ 1..=2) => p,
+                _ => return Err(format!("Line {}: part must be 1 or 2", k + 1)),
+            };
+
+            entries.insert((name.to_string(), part), answer.trim().to_string());
+        }
+
+        Ok(Self { entries })
+    }
+
+    pub fn get(&self, input: &str, part: u8) -> Option<&str> {
+        self.entries
+            .get(&(input.to_string(), part))
+            .map(|a| a.as_str())
+    }
+}
+
+/// Reads the recorded answers, a missing file simply has no answers
+pub fn read_answers() -> io::Result<Answers> {
+    let filename = data_dir().join("answers.txt");
+    let data = match fs::read_to_string(filename) {
+        Ok(data) => data,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Answers::default()),
+        Err(err) => return Err(err),
+    };
+    Answers::parse(data.as_str()).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_answers() {
+        let data = "# day 7\n07 1 3749\n07 2 11387\n\n12-sample2 2 436\n";
+        let answers = Answers::parse(data).unwrap();
+        assert_eq!(answers.get("07", 1), Some("3749"));
+        assert_eq!(answers.get("07", 2), Some("11387"));
+        assert_eq!(answers.get("12-sample2", 2), Some("436"));
+        assert_eq!(answers.get("12-sample2", 1), None);
+    }
+
+    #[test]
+    fn test_parse_answers_invalid() {
+        assert!(Answers::parse("07 1").is_err());
+        assert!(Answers::parse("07 3 100").is_err());
+    }
+}
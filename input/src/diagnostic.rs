@@ -0,0 +1,144 @@
+use std::fmt;
+
+use crate::SolveError;
+
+/// A parse failure pinned to a spot in the input, rendered like a compiler diagnostic:
+///
+/// ```text
+/// expected an ascii digit, found `x`
+///  --> line 2, column 3
+///   |
+/// 2 | 5 x
+///   |   ^
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// 1-based line number
+    pub line: usize,
+    /// 1-based column, in characters
+    pub column: usize,
+    /// The offending line, without its line ending
+    pub source: String,
+    pub message: String,
+    /// Extra hint printed below the snippet, ie: which part of the format was being parsed
+    pub note: Option<String>,
+}
+
+impl Diagnostic {
+    /// Diagnostic at a byte offset of the input
+    pub fn at(input: &str, offset: usize, message: impl Into<String>) -> Self {
+        let offset = offset.min(input.len());
+        let line_start = input[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let line = input[..line_start].matches('\n').count();
+        let column = input[line_start..offset].chars().count();
+        Self::at_line(input, line, column, message)
+    }
+
+    /// Diagnostic at a 0-based line and character column, for parsers walking the input by hand
+    pub fn at_line(input: &str, line: usize, column: usize, message: impl Into<String>) -> Self {
+        let source = input.lines().nth(line).unwrap_or("");
+        Self {
+            line: line + 1,
+            column: column + 1,
+            source: source.trim_end_matches('\r').to_string(),
+            message: message.into(),
+            note: None,
+        }
+    }
+
+    pub fn with_note(mut self, note: impl Into<String>) -> Self {
+        self.note = Some(note.into());
+        self
+    }
+
+    /// Sets the message to "expected `what`, found ..." with whatever sits under the caret
+    pub fn expected(mut self, what: &str) -> Self {
+        self.message = format!("expected {}, found {}", what, self.found());
+        self
+    }
+
+    /// Describes what sits under the caret, for messages like "expected X, found Y"
+    pub fn found(&self) -> String {
+        match self.source.chars().nth(self.column - 1) {
+            Some(c) => format!("`{}`", c.escape_debug()),
+            None => "end of line".to_string(),
+        }
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = self.line.to_string().len();
+        // Keep tabs so the caret lines up with the source
+        let padding: String = self
+            .source
+            .chars()
+            .take(self.column - 1)
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect();
+
+        writeln!(f, "{}", self.message)?;
+        writeln!(
+            f,
+            "{:w$}--> line {}, column {}",
+            "",
+            self.line,
+            self.column,
+            w = width
+        )?;
+        writeln!(f, "{:w$} |", "", w = width)?;
+        writeln!(f, "{} | {}", self.line, self.source)?;
+        write!(f, "{:w$} | {}^", "", padding, w = width)?;
+        if let Some(note) = self.note.as_ref() {
+            write!(f, "\n{:w$} = note: {}", "", note, w = width)?;
+        }
+        Ok(())
+    }
+}
+
+impl From<Diagnostic> for SolveError {
+    fn from(diagnostic: Diagnostic) -> Self {
+        SolveError::Parse(diagnostic.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_at() {
+        let input = "1 2\n5 x\n";
+        let diagnostic = Diagnostic::at(input, 6, "expected an ascii digit");
+        assert_eq!(diagnostic.line, 2);
+        assert_eq!(diagnostic.column, 3);
+        assert_eq!(diagnostic.source, "5 x");
+        assert_eq!(diagnostic.found(), "`x`");
+        assert_eq!(
+            diagnostic.expected("`:`").message,
+            "expected `:`, found `x`"
+        );
+
+        let end = Diagnostic::at(input, input.len(), "expected a line");
+        assert_eq!((end.line, end.column), (3, 1));
+        assert_eq!(end.found(), "end of line");
+    }
+
+    #[test]
+    fn test_display() {
+        let diagnostic = Diagnostic::at_line("..#\n.\tX.\n", 1, 2, "invalid cell `X`")
+            .with_note("cells are one of `.#`");
+        assert_eq!(
+            diagnostic.to_string(),
+            [
+                "invalid cell `X`",
+                " --> line 2, column 3",
+                "  |",
+                "2 | .\tX.",
+                "  |  \t^",
+                "  = note: cells are one of `.#`",
+            ]
+            .join("\n")
+        );
+    }
+}
@@ -0,0 +1,57 @@
+use std::error::Error;
+use std::fmt;
+
+/// Why a part could not be solved
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SolveError {
+    /// The input does not follow the puzzle format
+    Parse(String),
+    /// The input parsed but breaks a rule of the puzzle, ie: a missing guard
+    Validation(String),
+    /// The input is valid but has no answer
+    Unsolvable(String),
+    /// The answer or a value on the way to it does not fit in its integer type
+    Overflow(String),
+}
+
+impl SolveError {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            SolveError::Parse(_) => "parse error",
+            SolveError::Validation(_) => "invalid input",
+            SolveError::Unsolvable(_) => "unsolvable",
+            SolveError::Overflow(_) => "overflow",
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        match self {
+            SolveError::Parse(m)
+            | SolveError::Validation(m)
+            | SolveError::Unsolvable(m)
+            | SolveError::Overflow(m) => m,
+        }
+    }
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.kind(), self.message())
+    }
+}
+
+impl Error for SolveError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let err = SolveError::Validation("guard is missing".to_string());
+        assert_eq!(
+            err.to_string(),
+            "invalid input: guard is missing".to_string()
+        );
+    }
+}
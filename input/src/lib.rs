@@ -1,26 +1,70 @@
+mod answer;
+mod answers;
+mod diagnostic;
+mod error;
+mod parse;
+mod sample;
+mod solution;
+mod stopwatch;
+
+use std::io;
 use std::path::Path;
 use std::time::Duration;
 use std::{fs, path::PathBuf};
 
-pub fn get_puzzle_input(name: &str) -> String {
+pub use answer::Answer;
+pub use answers::{read_answers, Answers};
+pub use diagnostic::Diagnostic;
+pub use error::SolveError;
+pub use parse::{end_of_line, lines, parse_all, ParseResult};
+pub use sample::check_sample;
+pub use solution::{parse_puzzle, Puzzle, Solution};
+pub use stopwatch::{Phase, Stopwatch};
+
+/// Directory holding the puzzle inputs, next to the workspace crates
+pub fn data_dir() -> PathBuf {
+    let crate_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    crate_dir.parent().unwrap_or(crate_dir).join("data")
+}
+
+pub fn puzzle_path(name: &str) -> PathBuf {
     let file = format!("day{}.txt", name);
-    let filename: PathBuf = Path::new("..").join("data").join(file);
-    fs::read_to_string(filename).unwrap()
+    data_dir().join(file)
+}
+
+pub fn read_puzzle_input(name: &str) -> io::Result<String> {
+    fs::read_to_string(puzzle_path(name))
+}
+
+pub fn get_puzzle_input(name: &str) -> String {
+    read_puzzle_input(name).unwrap()
 }
 
 pub fn format_duration(duration: Duration) -> String {
-    let micros = duration.as_micros();
-    if micros < 10_000 {
-        return format!("{}µs", micros);
+    let nanos = duration.as_nanos();
+    if nanos < 1_000 {
+        return format!("{}ns", nanos);
+    }
+    if nanos < 1_000_000 {
+        return format!("{}µs", format_fraction(nanos as f64 / 1_000.0));
     }
     let millis = duration.as_millis();
     if millis < 10_000 {
-        return format!("{}ms", millis);
+        return format!("{}ms", format_fraction(nanos as f64 / 1_000_000.0));
     }
     let seconds = duration.as_secs();
     format!("{}s", seconds)
 }
 
+/// Formats with up to 2 decimal places, without trailing zeroes
+fn format_fraction(value: f64) -> String {
+    let formatted = format!("{:.2}", value);
+    formatted
+        .trim_end_matches('0')
+        .trim_end_matches('.')
+        .to_string()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -36,4 +80,32 @@ mod tests {
         let duration = Duration::new(1, 400_000_000);
         assert_eq!(format_duration(duration), "1400ms".to_string());
     }
+
+    #[test]
+    fn test_duration_nanos() {
+        let duration = Duration::new(0, 850);
+        assert_eq!(format_duration(duration), "850ns".to_string());
+    }
+
+    #[test]
+    fn test_duration_fractions() {
+        assert_eq!(
+            format_duration(Duration::new(0, 1_500)),
+            "1.5µs".to_string()
+        );
+        assert_eq!(
+            format_duration(Duration::new(0, 2_360_000)),
+            "2.36ms".to_string()
+        );
+        assert_eq!(
+            format_duration(Duration::new(0, 12_345_678)),
+            "12.35ms".to_string()
+        );
+    }
+
+    #[test]
+    fn test_duration_seconds() {
+        let duration = Duration::new(12, 500_000_000);
+        assert_eq!(format_duration(duration), "12s".to_string());
+    }
 }
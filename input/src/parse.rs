@@ -0,0 +1,206 @@
+use std::error::Error;
+
+use nom::character::complete::{line_ending, multispace0};
+use nom::combinator::eof;
+use nom::{IResult, Parser};
+use nom_supreme::error::{BaseErrorKind, ErrorTree, Expectation, StackContext};
+use nom_supreme::multi::collect_separated_terminated;
+use nom_supreme::ParserExt;
+
+use crate::{Diagnostic, SolveError};
+
+/// Result of the nom parsers, errors keep enough detail to point at the offending input
+pub type ParseResult<'a, O> = IResult<&'a str, O, ErrorTree<&'a str>>;
+
+/// Runs a nom parser over the whole input, only trailing whitespace may be left over
+pub fn parse_all<'a, O, P>(input: &'a str, mut parser: P) -> Result<O, SolveError>
+where
+    P: Parser<&'a str, O, ErrorTree<&'a str>>,
+{
+    match parser.parse(input) {
+        Ok((rest, output)) if rest.trim().is_empty() => Ok(output),
+        Ok((rest, _)) => {
+            // Lists stop before the separator, point at the offending line instead
+            let rest = rest.trim_start_matches(['\r', '\n']);
+            Err(diagnose(input, rest, &["end of input".to_string()], None).into())
+        }
+        Err(nom::Err::Error(err)) | Err(nom::Err::Failure(err)) => {
+            let failure = furthest(&err);
+            Err(diagnose(input, failure.rest, &failure.expected, failure.context).into())
+        }
+        Err(nom::Err::Incomplete(_)) => Err(SolveError::Parse("incomplete input".to_string())),
+    }
+}
+
+/// What the parser expected at the spot it got the furthest
+struct Failure<'a> {
+    rest: &'a str,
+    expected: Vec<String>,
+    context: Option<&'static str>,
+}
+
+/// One `item` per line until the end of the input.
+///
+/// Unlike `separated_list1`, a line that doesn't parse is an error instead of the end of the list.
+pub fn lines<'a, O, P>(item: P) -> impl Parser<&'a str, Vec<O>, ErrorTree<&'a str>>
+where
+    P: Parser<&'a str, O, ErrorTree<&'a str>>,
+{
+    collect_separated_terminated(item, line_ending, multispace0.all_consuming())
+}
+
+/// Matches the end of a line without consuming it, to terminate lists within a line
+pub fn end_of_line(input: &str) -> ParseResult<'_, ()> {
+    line_ending.peek().value(()).or(eof.value(())).parse(input)
+}
+
+fn furthest<'a>(err: &ErrorTree<&'a str>) -> Failure<'a> {
+    match err {
+        ErrorTree::Base { location, kind } => Failure {
+            rest: location,
+            expected: vec![describe(kind)],
+            context: None,
+        },
+        ErrorTree::Stack { base, contexts } => {
+            let mut failure = furthest(base);
+            // Contexts are listed innermost first
+            failure.context = failure
+                .context
+                .or(contexts.iter().find_map(|(_, c)| match c {
+                    StackContext::Context(c) => Some(*c),
+                    StackContext::Kind(_) => None,
+                }));
+            failure
+        }
+        ErrorTree::Alt(siblings) => {
+            let failures: Vec<Failure> = siblings.iter().map(furthest).collect();
+            let shortest = failures.iter().map(|f| f.rest.len()).min().unwrap_or(0);
+            let mut merged: Option<Failure> = None;
+            for failure in failures.into_iter().filter(|f| f.rest.len() == shortest) {
+                match merged.as_mut() {
+                    None => merged = Some(failure),
+                    Some(m) => {
+                        for e in failure.expected {
+                            if !m.expected.contains(&e) {
+                                m.expected.push(e);
+                            }
+                        }
+                        m.context = m.context.or(failure.context);
+                    }
+                }
+            }
+            merged.expect("Alt has at least one sibling")
+        }
+    }
+}
+
+fn describe(kind: &BaseErrorKind<&'static str, Box<dyn Error + Send + Sync>>) -> String {
+    match kind {
+        BaseErrorKind::Expected(Expectation::Tag(tag)) => format!("`{}`", tag.escape_debug()),
+        BaseErrorKind::Expected(Expectation::Char(c)) => format!("`{}`", c.escape_debug()),
+        BaseErrorKind::Expected(Expectation::Eof) => "end of input".to_string(),
+        BaseErrorKind::Expected(Expectation::Something) => "more input".to_string(),
+        BaseErrorKind::Expected(Expectation::CrLf) => "a line break".to_string(),
+        BaseErrorKind::Expected(expectation) => expectation.to_string(),
+        BaseErrorKind::Kind(kind) => kind.description().to_lowercase(),
+        BaseErrorKind::External(err) => err.to_string(),
+    }
+}
+
+fn diagnose(input: &str, rest: &str, expected: &[String], context: Option<&str>) -> Diagnostic {
+    let offset = input.len() - rest.len();
+    let mut diagnostic = Diagnostic::at(input, offset, "").expected(&one_of(expected));
+    if rest.is_empty() {
+        diagnostic.message = format!("expected {}, found end of input", one_of(expected));
+    }
+    match context {
+        Some(context) => diagnostic.with_note(format!("while parsing {}", context)),
+        None => diagnostic,
+    }
+}
+
+/// Joins alternatives as "a, b or c"
+fn one_of(items: &[String]) -> String {
+    match items.split_last() {
+        Some((last, [])) => last.clone(),
+        Some((last, rest)) => format!("{} or {}", rest.join(", "), last),
+        None => "something else".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nom::character::complete::{self, space1};
+    use nom::sequence::separated_pair;
+    use nom_supreme::tag::complete::tag;
+
+    use super::*;
+
+    fn numbers(input: &str) -> Result<Vec<u32>, SolveError> {
+        parse_all(
+            input,
+            nom::multi::separated_list1(line_ending, complete::u32),
+        )
+    }
+
+    fn pairs(input: &str) -> ParseResult<'_, Vec<(u32, u32)>> {
+        let pair = separated_pair(complete::u32, space1.or(tag(",")), complete::u32);
+        lines(pair.context("a pair")).parse(input)
+    }
+
+    #[test]
+    fn test_parse_all() {
+        assert_eq!(numbers("1\n2\n3\n"), Ok(vec![1, 2, 3]));
+        assert_eq!(
+            numbers("1\n2\nthree\n"),
+            Err(SolveError::Parse(
+                [
+                    "expected end of input, found `t`",
+                    " --> line 3, column 1",
+                    "  |",
+                    "3 | three",
+                    "  | ^",
+                ]
+                .join("\n")
+            ))
+        );
+        assert_eq!(parse_all("1 2\n3,4\n", pairs), Ok(vec![(1, 2), (3, 4)]));
+    }
+
+    #[test]
+    fn test_end_of_line() {
+        let row = || collect_separated_terminated(complete::u32, space1, end_of_line);
+        assert_eq!(parse_all("1 2 3\n", row()), Ok(vec![1, 2, 3]));
+        assert_eq!(
+            parse_all("1 2\n4 5", lines(row())),
+            Ok(vec![vec![1, 2], vec![4, 5]])
+        );
+
+        let err = parse_all("1 2 x\n", row()).unwrap_err();
+        assert!(err
+            .message()
+            .starts_with("expected an ascii digit, found `x`"));
+    }
+
+    #[test]
+    fn test_parse_all_diagnostic() {
+        let err = parse_all("1 2\n3;4\n", pairs).unwrap_err();
+        assert_eq!(
+            err.message(),
+            [
+                "expected a space or tab or `,`, found `;`",
+                " --> line 2, column 2",
+                "  |",
+                "2 | 3;4",
+                "  |  ^",
+                "  = note: while parsing a pair",
+            ]
+            .join("\n")
+        );
+
+        let err = parse_all("1 2\n3", pairs).unwrap_err();
+        assert!(err
+            .message()
+            .starts_with("expected a space or tab or `,`, found end of input"));
+    }
+}
@@ -0,0 +1,86 @@
+use crate::{get_puzzle_input, Answer, Solution};
+
+/// Solves one part of a sample input and asserts the answer, ie: `check_sample::<Day01>("01-sample", 1, 11)`
+pub fn check_sample<S: Solution>(sample: &str, part: u8, expected: impl Into<Answer>) {
+    let input = get_puzzle_input(sample);
+    let parsed = S::parse(&input).unwrap_or_else(|err| panic!("{}: {}", sample, err));
+    let answer = match part {
+        1 => S::part1(&parsed),
+        2 => S::part2(&parsed),
+        _ => panic!("Part must be 1 or 2"),
+    };
+    let answer = answer.unwrap_or_else(|err| panic!("{} part {}: {}", sample, part, err));
+    assert_eq!(answer, expected.into(), "{} part {}", sample, part);
+}
+
+/// Declares one test per row of a table of samples, solved through the day's `Solution`.
+/// Rows are `name: "sample", part N => expected`, with an optional `, ignore = "reason"`
+/// for samples known to fail:
+///
+/// ```text
+/// input::sample_tests! {
+///     Day12;
+///     test_part1: "12-sample", part 1 => 140;
+///     test_part2_sample3: "12-sample3", part 2 => 1206, ignore = "sides are miscounted";
+/// }
+/// ```
+#[macro_export]
+macro_rules! sample_tests {
+    (
+        $solution:ty;
+        $(
+            $name:ident: $sample:literal, part $part:literal => $expected:expr
+            $(, ignore = $reason:literal)?;
+        )*
+    ) => {
+        $(
+            #[test]
+            $(#[ignore = $reason])?
+            fn $name() {
+                $crate::check_sample::<$solution>($sample, $part, $expected);
+            }
+        )*
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::SolveError;
+
+    use super::*;
+
+    /// Counts the characters of the input, or of its first line
+    struct Length;
+
+    impl Solution for Length {
+        type Parsed = String;
+
+        fn parse(input: &str) -> Result<Self::Parsed, SolveError> {
+            Ok(input.to_string())
+        }
+
+        fn part1(parsed: &Self::Parsed) -> Result<Answer, SolveError> {
+            Ok(parsed.len().into())
+        }
+
+        fn part2(parsed: &Self::Parsed) -> Result<Answer, SolveError> {
+            Ok(parsed.lines().next().unwrap_or("").len().into())
+        }
+    }
+
+    fn sample_len() -> usize {
+        get_puzzle_input("00-sample").len()
+    }
+
+    sample_tests! {
+        Length;
+        test_sample_part1: "00-sample", part 1 => sample_len();
+        test_sample_ignored: "00-sample", part 2 => usize::MAX, ignore = "never matches";
+    }
+
+    #[test]
+    #[should_panic(expected = "00-sample part 1")]
+    fn test_sample_mismatch() {
+        check_sample::<Length>("00-sample", 1, sample_len() + 1);
+    }
+}
@@ -0,0 +1,91 @@
+use crate::{Answer, SolveError};
+
+/// A day's solution, split into parsing and solving so that
+/// both parts can share a single parsed input
+pub trait Solution {
+    /// Puzzle input after parsing
+    type Parsed;
+
+    fn parse(input: &str) -> Result<Self::Parsed, SolveError>;
+
+    fn part1(parsed: &Self::Parsed) -> Result<Answer, SolveError>;
+
+    fn part2(parsed: &Self::Parsed) -> Result<Answer, SolveError>;
+}
+
+/// A parsed puzzle with its day type erased so that
+/// different days can be stored side by side, and its parts solved on separate threads
+pub trait Puzzle: Send + Sync {
+    fn part1(&self) -> Result<Answer, SolveError>;
+
+    fn part2(&self) -> Result<Answer, SolveError>;
+
+    fn part(&self, part: u8) -> Result<Answer, SolveError> {
+        match part {
+            1 => self.part1(),
+            2 => self.part2(),
+            _ => Err(SolveError::Validation(format!(
+                "part {} does not exist, puzzles have parts 1 and 2",
+                part
+            ))),
+        }
+    }
+}
+
+struct Parsed<S: Solution>(S::Parsed);
+
+impl<S> Puzzle for Parsed<S>
+where
+    S: Solution,
+    S::Parsed: Send + Sync,
+{
+    fn part1(&self) -> Result<Answer, SolveError> {
+        S::part1(&self.0)
+    }
+
+    fn part2(&self) -> Result<Answer, SolveError> {
+        S::part2(&self.0)
+    }
+}
+
+/// Parses the input with the given solution and erases its type
+pub fn parse_puzzle<S>(input: &str) -> Result<Box<dyn Puzzle>, SolveError>
+where
+    S: Solution + 'static,
+    S::Parsed: Send + Sync + 'static,
+{
+    let parsed = S::parse(input)?;
+    Ok(Box::new(Parsed::<S>(parsed)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Counts the lines of the input
+    struct Lines;
+
+    impl Solution for Lines {
+        type Parsed = usize;
+
+        fn parse(input: &str) -> Result<Self::Parsed, SolveError> {
+            Ok(input.lines().count())
+        }
+
+        fn part1(parsed: &Self::Parsed) -> Result<Answer, SolveError> {
+            Ok((*parsed).into())
+        }
+
+        fn part2(parsed: &Self::Parsed) -> Result<Answer, SolveError> {
+            Ok((*parsed * 2).into())
+        }
+    }
+
+    #[test]
+    fn test_part() {
+        let puzzle = parse_puzzle::<Lines>("a\nb\n").unwrap();
+        assert_eq!(puzzle.part(1), Ok(2.into()));
+        assert_eq!(puzzle.part(2), Ok(4.into()));
+        assert!(matches!(puzzle.part(3), Err(SolveError::Validation(_))));
+    }
+}
@@ -0,0 +1,101 @@
+use std::time::{Duration, Instant};
+
+use crate::format_duration;
+
+/// A named, timed step of a run
+#[derive(Debug, Clone, PartialEq)]
+pub struct Phase {
+    pub name: &'static str,
+    pub duration: Duration,
+}
+
+/// Records how long each phase of a run takes, ie: read, parse and solve
+#[derive(Debug, Clone, Default)]
+pub struct Stopwatch {
+    phases: Vec<Phase>,
+}
+
+impl Stopwatch {
+    pub fn new() -> Self {
+        Self { phases: Vec::new() }
+    }
+
+    /// Runs `f` and records its duration under the phase name
+    pub fn time<T, F>(&mut self, name: &'static str, f: F) -> T
+    where
+        F: FnOnce() -> T,
+    {
+        let ts = Instant::now();
+        let result = f();
+        self.record(name, ts.elapsed());
+        result
+    }
+
+    /// Records a phase measured elsewhere
+    pub fn record(&mut self, name: &'static str, duration: Duration) {
+        self.phases.push(Phase { name, duration });
+    }
+
+    pub fn phases(&self) -> &[Phase] {
+        &self.phases
+    }
+
+    /// Total duration of the named phase, None if it never ran
+    pub fn get(&self, name: &str) -> Option<Duration> {
+        self.phases
+            .iter()
+            .filter(|p| p.name == name)
+            .map(|p| p.duration)
+            .reduce(|a, b| a + b)
+    }
+
+    pub fn total(&self) -> Duration {
+        self.phases.iter().map(|p| p.duration).sum()
+    }
+
+    /// Human readable list of phases, ie: `read: 12µs, parse: 40µs, solve: 1.2ms`
+    pub fn report(&self) -> String {
+        self.phases
+            .iter()
+            .map(|p| format!("{}: {}", p.name, format_duration(p.duration)))
+            .collect::<Vec<String>>()
+            .join(", ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_time_phase() {
+        let mut stopwatch = Stopwatch::new();
+        let value = stopwatch.time("parse", || 40 + 2);
+        assert_eq!(value, 42);
+        assert_eq!(stopwatch.phases().len(), 1);
+        assert_eq!(stopwatch.phases()[0].name, "parse");
+        assert!(stopwatch.get("parse").is_some());
+        assert!(stopwatch.get("solve").is_none());
+    }
+
+    #[test]
+    fn test_report() {
+        let mut stopwatch = Stopwatch::new();
+        stopwatch.record("read", Duration::from_nanos(800));
+        stopwatch.record("parse", Duration::from_micros(40));
+        stopwatch.record("solve", Duration::from_micros(1250));
+        assert_eq!(
+            stopwatch.report(),
+            "read: 800ns, parse: 40µs, solve: 1.25ms".to_string()
+        );
+        assert_eq!(stopwatch.total(), Duration::from_nanos(1_290_800));
+    }
+
+    #[test]
+    fn test_get_sums_repeated_phases() {
+        let mut stopwatch = Stopwatch::new();
+        stopwatch.record("solve", Duration::from_micros(10));
+        stopwatch.record("solve", Duration::from_micros(5));
+        assert_eq!(stopwatch.get("solve"), Some(Duration::from_micros(15)));
+    }
+}
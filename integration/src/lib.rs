@@ -0,0 +1,250 @@
+//! Checks both parts of every day registered in `aoc::days` against every input with a recorded
+//! answer.
+//!
+//! Real inputs take their answers from `data/answers.txt`, samples from `samples.txt`
+//! next to this crate. The `answers` test prints one report covering all of them.
+
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+
+use aoc::days::DAYS;
+use input::{data_dir, read_puzzle_input, Answer, Answers, SolveError};
+
+/// Answers given in the puzzle texts for the samples
+pub const SAMPLE_ANSWERS: &str = include_str!("../samples.txt");
+
+/// Inputs and parts the solutions are known to get wrong, with the reason
+pub const KNOWN_FAILURES: [(&str, u8, &str); 3] = [
+    (
+        "12-sample2",
+        2,
+        "the sides of holes inside a region are missed",
+    ),
+    (
+        "12-sample3",
+        2,
+        "sides are overcounted on the larger sample",
+    ),
+    ("12-sample5", 2, "holes touching at a corner are miscounted"),
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Status {
+    Pass,
+    Fail,
+    /// Wrong, as listed in `KNOWN_FAILURES`
+    KnownFailure(&'static str),
+    /// Right although listed in `KNOWN_FAILURES`, the entry should go
+    UnexpectedPass,
+    /// The part returned an error or panicked
+    Error(String),
+}
+
+#[derive(Debug)]
+pub struct Check {
+    pub day: u8,
+    /// Input name, ie: `07` or `12-sample2`
+    pub input: String,
+    pub part: u8,
+    pub expected: String,
+    pub actual: Option<Answer>,
+    pub status: Status,
+}
+
+impl Check {
+    pub fn failed(&self) -> bool {
+        matches!(
+            self.status,
+            Status::Fail | Status::UnexpectedPass | Status::Error(_)
+        )
+    }
+}
+
+/// Names of the inputs of a day found in `data`, the real input first then the samples
+pub fn inputs(day: u8) -> Vec<String> {
+    let real = format!("{:02}", day);
+    let sample_prefix = format!("{}-", real);
+
+    let mut names: Vec<String> = fs::read_dir(data_dir())
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter_map(|e| e.file_name().to_str().map(|n| n.to_string()))
+                .filter_map(|n| Some(n.strip_prefix("day")?.strip_suffix(".txt")?.to_string()))
+                .filter(|n| *n == real || n.starts_with(&sample_prefix))
+                .collect()
+        })
+        .unwrap_or_default();
+    names.sort();
+    names
+}
+
+/// Compares an answer with the recorded one, taking the known failures into account
+pub fn status(input: &str, part: u8, expected: &str, actual: &Answer) -> Status {
+    let known = KNOWN_FAILURES
+        .iter()
+        .find(|(name, p, _)| *name == input && *p == part)
+        .map(|(_, _, reason)| *reason);
+
+    match known {
+        None if actual.matches(expected) => Status::Pass,
+        None => Status::Fail,
+        Some(_) if actual.matches(expected) => Status::UnexpectedPass,
+        Some(reason) => Status::KnownFailure(reason),
+    }
+}
+
+/// Runs one part on an input, a panic counts as an error
+pub fn check_part<F>(day: u8, input: &str, part: u8, solve: F, expected: &str) -> Check
+where
+    F: Fn(&str) -> Result<Answer, SolveError>,
+{
+    let result = read_puzzle_input(input)
+        .map_err(|err| format!("unable to read input: {}", err))
+        .and_then(|data| {
+            panic::catch_unwind(AssertUnwindSafe(|| solve(&data)))
+                .map_err(|_| "panicked".to_string())?
+                .map_err(|err| err.to_string())
+        });
+
+    let (actual, status) = match result {
+        Ok(answer) => {
+            let status = status(input, part, expected, &answer);
+            (Some(answer), status)
+        }
+        Err(err) => (None, Status::Error(err)),
+    };
+    Check {
+        day,
+        input: input.to_string(),
+        part,
+        expected: expected.to_string(),
+        actual,
+        status,
+    }
+}
+
+/// Checks both parts of every day on every input found that has an answer
+/// recorded in either `answers` or `samples`
+pub fn check_all(answers: &Answers, samples: &Answers) -> Vec<Check> {
+    let mut checks: Vec<Check> = Vec::new();
+    for day in DAYS.iter() {
+        for input in inputs(day.day) {
+            for part in [1, 2] {
+                let expected = answers
+                    .get(&input, part)
+                    .or_else(|| samples.get(&input, part));
+                if let Some(expected) = expected {
+                    let solve = |data: &str| (day.parse)(data)?.part(part);
+                    checks.push(check_part(day.day, &input, part, solve, expected));
+                }
+            }
+        }
+    }
+    checks
+}
+
+/// One line per check and a summary, failures stand out in capitals
+pub fn report(checks: &[Check]) -> String {
+    let mut lines: Vec<String> = vec![format!(
+        "{:<4} {:<14} {:<4} {:<16} {:<16} Status",
+        "Day", "Input", "Part", "Expected", "Actual"
+    )];
+    for check in checks.iter() {
+        let actual = match check.actual.as_ref() {
+            Some(answer) => answer.to_string(),
+            None => "-".to_string(),
+        };
+        let status = match &check.status {
+            Status::Pass => "pass".to_string(),
+            Status::Fail => "FAIL".to_string(),
+            Status::KnownFailure(reason) => format!("known failure: {}", reason),
+            Status::UnexpectedPass => {
+                "UNEXPECTED PASS, remove it from the known failures".to_string()
+            }
+            Status::Error(err) => format!("ERROR: {}", err.lines().next().unwrap_or("")),
+        };
+        lines.push(format!(
+            "{:<4} {:<14} {:<4} {:<16} {:<16} {}",
+            format!("{:02}", check.day),
+            check.input,
+            check.part,
+            check.expected,
+            actual,
+            status
+        ));
+    }
+
+    let count = |f: fn(&Status) -> bool| checks.iter().filter(|c| f(&c.status)).count();
+    lines.push(format!(
+        "{} passed, {} failed, {} known failures",
+        count(|s| *s == Status::Pass),
+        checks.iter().filter(|c| c.failed()).count(),
+        count(|s| matches!(s, Status::KnownFailure(_))),
+    ));
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn answer_42(_: &str) -> Result<Answer, SolveError> {
+        Ok(42.into())
+    }
+
+    fn broken(_: &str) -> Result<Answer, SolveError> {
+        panic!("broken")
+    }
+
+    #[test]
+    fn test_status() {
+        let answer: Answer = 436.into();
+        assert_eq!(status("12", 2, "436", &answer), Status::Pass);
+        assert_eq!(status("12", 2, "80", &answer), Status::Fail);
+        assert_eq!(
+            status("12-sample2", 2, "436", &answer),
+            Status::UnexpectedPass
+        );
+        assert!(matches!(
+            status("12-sample2", 2, "100", &answer),
+            Status::KnownFailure(_)
+        ));
+    }
+
+    #[test]
+    fn test_check_part() {
+        let check = check_part(1, "01-sample", 1, answer_42, "42");
+        assert_eq!(check.status, Status::Pass);
+        assert!(!check.failed());
+
+        let check = check_part(1, "01-sample", 1, broken, "42");
+        assert_eq!(check.status, Status::Error("panicked".to_string()));
+        assert!(check.failed());
+
+        let check = check_part(1, "01-missing", 1, answer_42, "42");
+        assert!(matches!(check.status, Status::Error(_)));
+    }
+
+    #[test]
+    fn test_sample_answers() {
+        let samples = Answers::parse(SAMPLE_ANSWERS).unwrap();
+        assert_eq!(samples.get("07-sample", 2), Some("11387"));
+        for (input, part, _) in KNOWN_FAILURES.iter() {
+            assert!(
+                samples.get(input, *part).is_some(),
+                "{} part {}",
+                input,
+                part
+            );
+        }
+    }
+
+    #[test]
+    fn test_report() {
+        let checks = [check_part(1, "01-sample", 2, answer_42, "31")];
+        let report = report(&checks);
+        assert!(report.contains("01   01-sample      2    31               42               FAIL"));
+        assert!(report.ends_with("0 passed, 1 failed, 0 known failures"));
+    }
+}
@@ -0,0 +1,20 @@
+//! Runs every day on every input with a recorded answer and prints one report,
+//! failing when any part is wrong, errors or unexpectedly passes.
+
+use std::process::ExitCode;
+
+use input::{read_answers, Answers};
+use integration::{check_all, report, SAMPLE_ANSWERS};
+
+fn main() -> ExitCode {
+    let answers = read_answers().expect("unable to read data/answers.txt");
+    let samples = Answers::parse(SAMPLE_ANSWERS).expect("invalid samples.txt");
+
+    let checks = check_all(&answers, &samples);
+    println!("{}", report(&checks));
+
+    if checks.iter().any(|c| c.failed()) {
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
+}